@@ -0,0 +1,24 @@
+//! Prints the names of databases left over from previous runs, one per line
+//!
+//! An optional prefix can be passed as the first argument and defaults to `db_pool`.
+//!
+//! ```sh
+//! cargo run --example list_orphaned_databases --features postgres | xargs -I{} dropdb {}
+//! ```
+
+use db_pool::{sync::list_orphaned_databases, PrivilegedPostgresConfig};
+use dotenvy::dotenv;
+
+fn main() {
+    dotenv().ok();
+
+    let prefix = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "db_pool".to_owned());
+
+    let config = PrivilegedPostgresConfig::from_env().unwrap();
+
+    for db_name in list_orphaned_databases(config, prefix.as_str()).unwrap() {
+        println!("{db_name}");
+    }
+}
@@ -49,6 +49,11 @@ where
     ) -> Result<Self::PooledConnection<'pool>, Self::PoolError> {
         pool.get().await.map_err(Into::into)
     }
+
+    // deadpool establishes connections on demand only
+    fn min_idle(builder: Self::Builder, _: u32) -> Self::Builder {
+        builder
+    }
 }
 
 impl From<BuildError>
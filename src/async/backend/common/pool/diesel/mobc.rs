@@ -84,6 +84,10 @@ where
     ) -> Result<Self::PooledConnection<'pool>, Self::PoolError> {
         pool.get().await.map_err(|err| err.into().into())
     }
+
+    fn min_idle(builder: Self::Builder, min_idle: u32) -> Self::Builder {
+        builder.min_idle(u64::from(min_idle))
+    }
 }
 
 #[derive(Debug)]
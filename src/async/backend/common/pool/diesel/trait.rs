@@ -30,4 +30,5 @@ where
     async fn get_connection<'pool>(
         pool: &'pool Self::Pool,
     ) -> Result<Self::PooledConnection<'pool>, Self::PoolError>;
+    fn min_idle(builder: Self::Builder, min_idle: u32) -> Self::Builder;
 }
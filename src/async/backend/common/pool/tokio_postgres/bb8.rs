@@ -71,6 +71,10 @@ impl TokioPostgresPoolAssociation for TokioPostgresBb8 {
     ) -> Result<Self::PooledConnection<'pool>, Self::PoolError> {
         pool.get().await.map_err(Into::into)
     }
+
+    fn min_idle(builder: Builder<Manager>, min_idle: u32) -> Builder<Manager> {
+        builder.min_idle(Some(min_idle))
+    }
 }
 
 #[derive(Debug)]
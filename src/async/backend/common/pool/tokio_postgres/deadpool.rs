@@ -37,6 +37,11 @@ impl TokioPostgresPoolAssociation for TokioPostgresDeadpool {
     ) -> Result<Self::PooledConnection<'pool>, Self::PoolError> {
         pool.get().await.map(Into::into)
     }
+
+    // deadpool establishes connections on demand only
+    fn min_idle(builder: PoolBuilder<Manager>, _min_idle: u32) -> PoolBuilder<Manager> {
+        builder
+    }
 }
 
 pub struct PooledConnection(Object<Manager>);
@@ -71,6 +71,10 @@ impl TokioPostgresPoolAssociation for TokioPostgresMobc {
     ) -> Result<Connection<Manager>, PoolError> {
         pool.get().await.map_err(Into::into)
     }
+
+    fn min_idle(builder: Builder<Manager>, min_idle: u32) -> Builder<Manager> {
+        builder.min_idle(u64::from(min_idle))
+    }
 }
 
 #[derive(Debug)]
@@ -6,4 +6,43 @@ pub enum Error<B: Debug, P: Debug, C: Debug, Q: Debug> {
     Pool(P),
     Connection(C),
     Query(Q),
+    Statement(String, Q),
+    DatabaseExists(String),
+    UnknownTable(String),
+    PoolShutdown,
+    OperationPool(Operation, P),
+    MissingPrivileges(Vec<String>),
+    Unsupported(Operation),
+    /// Default connection pool circuit breaker was open during the operation,
+    /// failing it without trying to acquire a connection
+    CircuitOpen(Operation),
+    /// Entity creation stage at the given index dropped its connection
+    /// while creating entities in a transaction, rolling back previous stages
+    StageConnectionDropped(usize),
+    /// Generated database name was not the prefix followed by an underscore and lowercase `[a-z0-9_]`
+    /// within the length limit, failing creation before anything was created
+    InvalidDbName(String),
+}
+
+/// Lifecycle operation during which a connection could not be obtained from the default pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    /// Dropping databases left over from previous runs
+    DropPreviousDatabases,
+    /// Creating the entity template database
+    CreateEntityTemplate,
+    /// Creating the named database
+    Create(String),
+    /// Cleaning the named database
+    Clean(String),
+    /// Dropping the named database
+    Drop(String),
+    /// Snapshotting the named database
+    Snapshot(String),
+    /// Restoring the named database from its snapshot
+    Restore(String),
+    /// Rebuilding the connection pool of the named database
+    RebuildPool(String),
+    /// Checking whether the named database exists
+    CheckExists(String),
 }
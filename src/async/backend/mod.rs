@@ -26,12 +26,30 @@ pub use mysql::DieselAsyncMySQLBackend;
 pub use mysql::SeaORMMySQLBackend;
 #[cfg(feature = "sqlx-mysql")]
 pub use mysql::SqlxMySQLBackend;
+#[cfg(feature = "_async-postgres")]
+pub use postgres::DatabaseLocale;
+#[cfg(feature = "_async-postgres")]
+pub use postgres::DatabaseTemplate;
 #[cfg(feature = "diesel-async-postgres")]
 pub use postgres::DieselAsyncPostgresBackend;
+#[cfg(feature = "diesel-async-postgres")]
+pub use postgres::DieselAsyncSchemaPostgresBackend;
+#[cfg(feature = "_async-postgres")]
+pub use postgres::MaintenanceMode;
+#[cfg(feature = "_async-postgres")]
+pub use postgres::RestrictedPrivileges;
+#[cfg(feature = "_async-postgres")]
+pub use postgres::RoleOptions;
 #[cfg(feature = "sea-orm-postgres")]
 pub use postgres::SeaORMPostgresBackend;
 #[cfg(feature = "sqlx-postgres")]
 pub use postgres::SqlxPostgresBackend;
 #[cfg(feature = "tokio-postgres")]
 pub use postgres::TokioPostgresBackend;
+#[cfg(feature = "_async-postgres")]
+pub use postgres::cleanup_orphans;
+#[cfg(feature = "_async-postgres")]
+pub use postgres::{FkHandling, TruncateMode};
+#[cfg(feature = "_async-postgres")]
+pub use postgres::{Observer, Phase};
 pub use r#trait::Backend as BackendTrait;
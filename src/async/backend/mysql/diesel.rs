@@ -261,6 +261,7 @@ type BError<BuildError, PoolError> = BackendError<BuildError, PoolError, Connect
 #[async_trait]
 impl<P: DieselPoolAssociation<AsyncMysqlConnection>> Backend for DieselAsyncMySQLBackend<P> {
     type Pool = P::Pool;
+    type Connection = AsyncMysqlConnection;
 
     type BuildError = P::BuildError;
     type PoolError = P::PoolError;
@@ -285,6 +286,30 @@ impl<P: DieselPoolAssociation<AsyncMysqlConnection>> Backend for DieselAsyncMySQ
         MySQLBackendWrapper::new(self).clean(db_id).await
     }
 
+    async fn clean_tables(
+        &self,
+        db_id: uuid::Uuid,
+        table_names: &[&str],
+    ) -> Result<(), BError<P::BuildError, P::PoolError>> {
+        MySQLBackendWrapper::new(self)
+            .clean_tables(db_id, table_names)
+            .await
+    }
+
+    async fn reset_sequences(
+        &self,
+        db_id: uuid::Uuid,
+    ) -> Result<(), BError<P::BuildError, P::PoolError>> {
+        MySQLBackendWrapper::new(self).reset_sequences(db_id).await
+    }
+
+    async fn rebuild_pool(
+        &self,
+        db_id: uuid::Uuid,
+    ) -> Result<P::Pool, BError<P::BuildError, P::PoolError>> {
+        MySQLBackendWrapper::new(self).rebuild_pool(db_id).await
+    }
+
     async fn drop(
         &self,
         db_id: uuid::Uuid,
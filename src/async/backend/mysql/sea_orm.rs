@@ -264,6 +264,7 @@ type BError = BackendError<BuildError, PoolError, ConnectionError, QueryError>;
 #[async_trait]
 impl Backend for SeaORMMySQLBackend {
     type Pool = DatabaseConnection;
+    type Connection = DatabaseConnection;
 
     type BuildError = BuildError;
     type PoolError = PoolError;
@@ -288,6 +289,20 @@ impl Backend for SeaORMMySQLBackend {
         MySQLBackendWrapper::new(self).clean(db_id).await
     }
 
+    async fn clean_tables(&self, db_id: uuid::Uuid, table_names: &[&str]) -> Result<(), BError> {
+        MySQLBackendWrapper::new(self)
+            .clean_tables(db_id, table_names)
+            .await
+    }
+
+    async fn reset_sequences(&self, db_id: uuid::Uuid) -> Result<(), BError> {
+        MySQLBackendWrapper::new(self).reset_sequences(db_id).await
+    }
+
+    async fn rebuild_pool(&self, db_id: uuid::Uuid) -> Result<DatabaseConnection, BError> {
+        MySQLBackendWrapper::new(self).rebuild_pool(db_id).await
+    }
+
     async fn drop(&self, db_id: uuid::Uuid, _is_restricted: bool) -> Result<(), BError> {
         MySQLBackendWrapper::new(self).drop(db_id).await
     }
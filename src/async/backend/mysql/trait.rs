@@ -10,7 +10,7 @@ use uuid::Uuid;
 
 use crate::{common::statement::mysql, util::get_db_name};
 
-use super::super::error::Error as BackendError;
+use super::super::error::{Error as BackendError, Operation};
 
 #[async_trait]
 pub(super) trait MySQLBackend<'pool>: Send + Sync + 'static {
@@ -116,7 +116,9 @@ where
         // Drop previous databases if needed
         if self.get_drop_previous_databases() {
             // Get privileged connection
-            let conn = &mut self.get_connection().await.map_err(Into::into)?;
+            let conn = &mut self.get_connection().await.map_err(|err| {
+                BackendError::OperationPool(Operation::DropPreviousDatabases, err)
+            })?;
 
             // Get previous database names
             self.execute_query(mysql::USE_DEFAULT_DATABASE, conn)
@@ -131,7 +133,9 @@ where
             let futures = db_names
                 .drain(..)
                 .map(|db_name| async move {
-                    let conn = &mut self.get_connection().await.map_err(Into::into)?;
+                    let conn = &mut self.get_connection().await.map_err(|err| {
+                        BackendError::OperationPool(Operation::DropPreviousDatabases, err)
+                    })?;
                     self.execute_query(mysql::drop_database(db_name.as_str()).as_str(), conn)
                         .await
                         .map_err(Into::into)?;
@@ -165,7 +169,9 @@ where
         let host = self.get_host();
 
         // Get privileged connection
-        let conn = &mut self.get_connection().await.map_err(Into::into)?;
+        let conn = &mut self.get_connection().await.map_err(|err| {
+            BackendError::OperationPool(Operation::Create(db_name.to_owned()), err)
+        })?;
 
         // Create database
         self.execute_query(mysql::create_database(db_name).as_str(), conn)
@@ -210,6 +216,15 @@ where
         Ok(pool)
     }
 
+    pub(super) async fn rebuild_pool(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<B::Pool, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Create new connection pool with attached role
+        self.create_connection_pool(db_id).await.map_err(Into::into)
+    }
+
     pub(super) async fn clean(
         &'backend self,
         db_id: uuid::Uuid,
@@ -220,7 +235,9 @@ where
         let db_name = db_name.as_str();
 
         // Get privileged connection
-        let conn = &mut self.get_connection().await.map_err(Into::into)?;
+        let conn = &mut self.get_connection().await.map_err(|err| {
+            BackendError::OperationPool(Operation::Clean(db_name.to_owned()), err)
+        })?;
 
         // Get table names
         let table_names = self
@@ -251,6 +268,91 @@ where
         Ok(())
     }
 
+    pub(super) async fn clean_tables(
+        &'backend self,
+        db_id: uuid::Uuid,
+        table_names: &[&str],
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get database name based on UUID
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        // Get privileged connection
+        let conn = &mut self.get_connection().await.map_err(|err| {
+            BackendError::OperationPool(Operation::Clean(db_name.to_owned()), err)
+        })?;
+
+        // Get existing table names
+        let existing_table_names = self
+            .get_table_names(db_name, conn)
+            .await
+            .map_err(Into::into)?;
+
+        // Validate that all given tables exist
+        if let Some(table_name) = table_names
+            .iter()
+            .find(|table_name| !existing_table_names.iter().any(|name| name == *table_name))
+        {
+            return Err(BackendError::UnknownTable((*table_name).to_owned()));
+        }
+
+        // Generate truncate statements
+        let stmts = table_names
+            .iter()
+            .map(|table_name| mysql::truncate_table(table_name, db_name).into());
+
+        // Turn off foreign key checks
+        self.execute_query(mysql::TURN_OFF_FOREIGN_KEY_CHECKS, conn)
+            .await
+            .map_err(Into::into)?;
+
+        // Truncate tables
+        self.batch_execute_query(stmts, conn)
+            .await
+            .map_err(Into::into)?;
+
+        // Turn on foreign key checks
+        self.execute_query(mysql::TURN_ON_FOREIGN_KEY_CHECKS, conn)
+            .await
+            .map_err(Into::into)?;
+
+        Ok(())
+    }
+
+    pub(super) async fn reset_sequences(
+        &'backend self,
+        db_id: uuid::Uuid,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get database name based on UUID
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        // Get privileged connection
+        let conn = &mut self.get_connection().await.map_err(|err| {
+            BackendError::OperationPool(Operation::Clean(db_name.to_owned()), err)
+        })?;
+
+        // Get table names
+        let table_names = self
+            .get_table_names(db_name, conn)
+            .await
+            .map_err(Into::into)?;
+
+        // Generate auto increment reset statements
+        let stmts = table_names
+            .iter()
+            .map(|table_name| mysql::reset_auto_increment(table_name.as_str(), db_name).into());
+
+        // Reset auto increments to lowest value allowed by existing rows
+        self.batch_execute_query(stmts, conn)
+            .await
+            .map_err(Into::into)?;
+
+        Ok(())
+    }
+
     pub(super) async fn drop(
         &'backend self,
         db_id: uuid::Uuid,
@@ -263,7 +365,10 @@ where
         let host = self.get_host();
 
         // Get privileged connection
-        let conn = &mut self.get_connection().await.map_err(Into::into)?;
+        let conn = &mut self
+            .get_connection()
+            .await
+            .map_err(|err| BackendError::OperationPool(Operation::Drop(db_name.to_owned()), err))?;
 
         // Drop database
         self.execute_query(mysql::drop_database(db_name).as_str(), conn)
@@ -0,0 +1,127 @@
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+// Cap on doubling the cooldown after consecutive failed probes
+const MAX_BACKOFF_EXPONENT: u32 = 6;
+
+#[derive(Default)]
+struct State {
+    failures: u32,
+    trips: u32,
+    open_until: Option<Instant>,
+}
+
+/// Circuit breaker fast-failing acquisitions after consecutive failures
+///
+/// Once open, a single probe is let through after the cooldown,
+/// which doubles every time the probe fails and resets as soon as an acquisition succeeds.
+pub(super) struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    pub(super) fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            cooldown,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Runs the given acquisition unless the breaker is open, returning no error when fast-failing
+    pub(super) async fn call<T, E>(
+        &self,
+        acquire: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, Option<E>> {
+        {
+            let mut state = self.state.lock();
+            if let Some(open_until) = state.open_until {
+                let now = Instant::now();
+                if now < open_until {
+                    return Err(None);
+                }
+
+                // Keep breaker open for other callers while probing
+                state.open_until = Some(now + self.backoff(state.trips));
+            }
+        }
+
+        let result = acquire.await;
+
+        let mut state = self.state.lock();
+        if result.is_ok() {
+            *state = State::default();
+        } else {
+            state.failures = state.failures.saturating_add(1);
+            if state.failures >= self.threshold {
+                state.trips = state.trips.saturating_add(1);
+                state.open_until = Some(Instant::now() + self.backoff(state.trips - 1));
+            }
+        }
+        result.map_err(Some)
+    }
+
+    fn backoff(&self, trips: u32) -> Duration {
+        self.cooldown
+            .saturating_mul(1 << trips.min(MAX_BACKOFF_EXPONENT))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+
+    use super::CircuitBreaker;
+
+    const COOLDOWN: Duration = Duration::from_millis(50);
+
+    #[tokio::test]
+    async fn opens_after_consecutive_failures_and_probes_after_cooldown() {
+        let breaker = CircuitBreaker::new(3, COOLDOWN);
+        let attempts = AtomicU32::new(0);
+        let acquire = || async {
+            attempts.fetch_add(1, Ordering::Relaxed);
+            Err::<(), _>("pool unavailable")
+        };
+
+        // failures below threshold must reach pool
+        for _ in 0..3 {
+            assert_eq!(breaker.call(acquire()).await, Err(Some("pool unavailable")));
+        }
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+
+        // open breaker must fast-fail without reaching pool
+        assert_eq!(breaker.call(acquire()).await, Err(None));
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+
+        // probe must reach pool after cooldown and reopen breaker for twice as long
+        tokio::time::sleep(COOLDOWN).await;
+        assert_eq!(breaker.call(acquire()).await, Err(Some("pool unavailable")));
+        assert_eq!(attempts.load(Ordering::Relaxed), 4);
+        tokio::time::sleep(COOLDOWN).await;
+        assert_eq!(breaker.call(acquire()).await, Err(None));
+        assert_eq!(attempts.load(Ordering::Relaxed), 4);
+    }
+
+    #[tokio::test]
+    async fn closes_after_successful_probe() {
+        let breaker = CircuitBreaker::new(1, COOLDOWN);
+
+        assert_eq!(
+            breaker.call(async { Err::<(), _>(()) }).await,
+            Err(Some(()))
+        );
+        assert_eq!(breaker.call(async { Ok::<_, ()>(()) }).await, Err(None));
+
+        // successful probe must reset failures
+        tokio::time::sleep(COOLDOWN).await;
+        assert_eq!(breaker.call(async { Ok::<_, ()>(()) }).await, Ok(()));
+        assert_eq!(breaker.call(async { Ok::<_, ()>(()) }).await, Ok(()));
+    }
+}
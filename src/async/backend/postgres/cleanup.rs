@@ -0,0 +1,141 @@
+use tokio_postgres::{Config, Error, NoTls};
+
+use crate::{
+    common::{config::PrivilegedPostgresConfig, statement::postgres},
+    util::{is_valid_db_name_prefix, list_prefixed_db_names},
+};
+
+/// Drops databases left behind by previous runs with the given database name prefix
+/// along with their attached roles, returning their names
+///
+/// Connects to the default database as the privileged user without building a backend,
+/// forcing databases still in use to drop.
+/// Databases created by this crate are prefixed with `db_pool` unless configured otherwise.
+/// # Panics
+/// Panics if the prefix is not lowercase `[a-z0-9_]` starting with a letter or underscore,
+/// or is longer than 17 characters.
+pub async fn cleanup_orphans(
+    config: &PrivilegedPostgresConfig,
+    prefix: &str,
+) -> Result<Vec<String>, Error> {
+    assert!(
+        is_valid_db_name_prefix(prefix),
+        "invalid database name prefix {prefix:?}"
+    );
+
+    let config = config.default_connection_url().parse::<Config>()?;
+    let (client, connection) = config.connect(NoTls).await?;
+    tokio::spawn(connection);
+
+    // Get previous database names, skipping databases with other prefixes
+    let db_names = list_prefixed_db_names(
+        prefix,
+        client
+            .query(
+                postgres::GET_DATABASE_NAMES_WITH_PREFIX,
+                &[&format!("{prefix}_")],
+            )
+            .await?
+            .iter()
+            .map(|row| row.get::<_, String>(0)),
+    );
+
+    // Drop databases and their roles
+    for db_name in &db_names {
+        client
+            .batch_execute(
+                [
+                    postgres::force_drop_database(db_name),
+                    postgres::drop_role_if_exists(db_name),
+                ]
+                .join(";")
+                .as_str(),
+            )
+            .await?;
+    }
+
+    Ok(db_names)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use tokio_postgres::{Client, Config, NoTls};
+    use tokio_shared_rt::test;
+    use uuid::Uuid;
+
+    use crate::{
+        common::statement::postgres, tests::get_privileged_postgres_config, util::get_db_name,
+    };
+
+    use super::{super::r#trait::tests::PgDropLock, cleanup_orphans};
+
+    async fn connect() -> Client {
+        let config = get_privileged_postgres_config()
+            .default_connection_url()
+            .parse::<Config>()
+            .unwrap();
+        let (client, connection) = config.connect(NoTls).await.unwrap();
+        tokio::spawn(connection);
+        client
+    }
+
+    async fn database_exists(db_name: &str, client: &Client) -> bool {
+        client
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM pg_database WHERE datname = $1)",
+                &[&db_name],
+            )
+            .await
+            .unwrap()
+            .get(0)
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn cleans_up_orphaned_databases() {
+        const NUM_DBS: usize = 3;
+
+        async {
+            let client = connect().await;
+
+            // create stray databases with attached roles
+            let mut db_names = Vec::new();
+            for _ in 0..NUM_DBS {
+                let db_name = get_db_name(Uuid::new_v4());
+                client
+                    .batch_execute(
+                        [
+                            postgres::create_database(db_name.as_str()),
+                            postgres::create_role(db_name.as_str(), db_name.as_str(), None),
+                        ]
+                        .join(";")
+                        .as_str(),
+                    )
+                    .await
+                    .unwrap();
+                db_names.push(db_name);
+            }
+
+            let removed = cleanup_orphans(get_privileged_postgres_config(), "db_pool")
+                .await
+                .unwrap();
+
+            // stray databases must have been reported and dropped
+            for db_name in &db_names {
+                assert!(removed.contains(db_name));
+                assert!(!database_exists(db_name, &client).await);
+            }
+        }
+        .lock_drop()
+        .await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    #[should_panic(expected = "invalid database name prefix")]
+    async fn rejects_invalid_prefix() {
+        cleanup_orphans(get_privileged_postgres_config(), "db-pool")
+            .await
+            .unwrap();
+    }
+}
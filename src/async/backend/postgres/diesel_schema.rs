@@ -0,0 +1,766 @@
+use std::pin::Pin;
+
+use async_trait::async_trait;
+use diesel::{ConnectionError, prelude::*, result::Error, sql_query, sql_types::Text};
+use diesel_async::{
+    AsyncConnection, AsyncPgConnection, RunQueryDsl, SimpleAsyncConnection,
+    pooled_connection::AsyncDieselConnectionManager,
+};
+use futures::Future;
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        config::postgres::{PrivilegedPostgresConfig, RestrictedConnectionParams},
+        statement::postgres,
+    },
+    util::{
+        DEFAULT_DB_NAME_PREFIX, get_prefixed_db_name, is_prefixed_db_name, is_valid_db_name_prefix,
+    },
+};
+
+use super::{
+    super::{
+        common::pool::diesel::r#trait::DieselPoolAssociation,
+        error::{Error as BackendError, Operation},
+        r#trait::Backend,
+    },
+    r#trait::matches_table_name,
+};
+
+type CreateEntities = dyn Fn(
+        AsyncPgConnection,
+    ) -> Pin<Box<dyn Future<Output = Option<AsyncPgConnection>> + Send + 'static>>
+    + Send
+    + Sync
+    + 'static;
+
+#[derive(QueryableByName)]
+struct SchemaName {
+    #[diesel(sql_type = Text)]
+    schema_name: String,
+}
+
+#[derive(QueryableByName)]
+struct TableName {
+    #[diesel(sql_type = Text)]
+    table_name: String,
+}
+
+#[derive(QueryableByName)]
+struct SequenceName {
+    #[diesel(sql_type = Text)]
+    sequence_name: String,
+}
+
+/// [`Diesel async Postgres`](https://docs.rs/diesel-async/0.5.2/diesel_async/struct.AsyncPgConnection.html) backend
+/// isolating each database in its own schema of a shared database instead of creating a database per test
+pub struct DieselAsyncSchemaPostgresBackend<P: DieselPoolAssociation<AsyncPgConnection>> {
+    privileged_config: PrivilegedPostgresConfig,
+    database: String,
+    default_pool: P::Pool,
+    create_restricted_pool: Box<
+        dyn Fn(AsyncDieselConnectionManager<AsyncPgConnection>) -> P::Builder
+            + Send
+            + Sync
+            + 'static,
+    >,
+    create_entities: Box<CreateEntities>,
+    drop_previous_schemas_flag: bool,
+    privileged_role_flag: bool,
+    schema_name_prefix: String,
+}
+
+impl<P: DieselPoolAssociation<AsyncPgConnection>> DieselAsyncSchemaPostgresBackend<P> {
+    /// Creates a new [`Diesel async Postgres`](https://docs.rs/diesel-async/0.5.2/diesel_async/struct.AsyncPgConnection.html)
+    /// backend creating schemas in the given shared database
+    /// # Example
+    /// ```
+    /// use bb8::Pool;
+    /// use db_pool::{
+    ///     r#async::{DieselAsyncSchemaPostgresBackend, DieselBb8},
+    ///     PrivilegedPostgresConfig,
+    /// };
+    /// use diesel::sql_query;
+    /// use diesel_async::RunQueryDsl;
+    /// use dotenvy::dotenv;
+    ///
+    /// async fn f() {
+    ///     dotenv().ok();
+    ///
+    ///     let config = PrivilegedPostgresConfig::from_env().unwrap();
+    ///
+    ///     let backend = DieselAsyncSchemaPostgresBackend::<DieselBb8>::new(
+    ///         config,
+    ///         "postgres".to_owned(),
+    ///         |_| Pool::builder().max_size(10),
+    ///         |_| Pool::builder().max_size(2),
+    ///         move |mut conn| {
+    ///             Box::pin(async {
+    ///                 sql_query("CREATE TABLE book(id SERIAL PRIMARY KEY, title TEXT NOT NULL)")
+    ///                     .execute(&mut conn)
+    ///                     .await
+    ///                     .unwrap();
+    ///                 Some(conn)
+    ///             })
+    ///         },
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// }
+    ///
+    /// tokio_test::block_on(f());
+    /// ```
+    /// Entities are created with the search path set to the created schema,
+    /// so unqualified table names resolve to it.
+    pub async fn new(
+        privileged_config: PrivilegedPostgresConfig,
+        database: String,
+        create_privileged_pool: impl Fn(AsyncDieselConnectionManager<AsyncPgConnection>) -> P::Builder,
+        create_restricted_pool: impl Fn(AsyncDieselConnectionManager<AsyncPgConnection>) -> P::Builder
+        + Send
+        + Sync
+        + 'static,
+        create_entities: impl Fn(
+            AsyncPgConnection,
+        ) -> Pin<
+            Box<dyn Future<Output = Option<AsyncPgConnection>> + Send + 'static>,
+        > + Send
+        + Sync
+        + 'static,
+    ) -> Result<Self, P::BuildError> {
+        let manager = || {
+            AsyncDieselConnectionManager::new(
+                privileged_config.privileged_database_connection_url(database.as_str()),
+            )
+        };
+
+        // Both create and build functions take manager value as a parameter,
+        // but only one should actually use it (depends on the particular connection pool API)
+        let builder = create_privileged_pool(manager());
+
+        let default_pool = P::build_pool(builder, manager()).await?;
+
+        Ok(Self {
+            privileged_config,
+            database,
+            default_pool,
+            create_restricted_pool: Box::new(create_restricted_pool),
+            create_entities: Box::new(create_entities),
+            drop_previous_schemas_flag: true,
+            privileged_role_flag: false,
+            schema_name_prefix: DEFAULT_DB_NAME_PREFIX.to_owned(),
+        })
+    }
+
+    /// Drop schemas created in previous runs upon initialization
+    #[must_use]
+    pub fn drop_previous_schemas(self, value: bool) -> Self {
+        Self {
+            drop_previous_schemas_flag: value,
+            ..self
+        }
+    }
+
+    /// Connect to schemas as the privileged role instead of creating a role attached to each schema
+    ///
+    /// Only the ``CREATE`` privilege on the shared database is required,
+    /// e.g. on managed servers where the privileged role cannot create databases or roles.
+    /// Privileges cannot be restricted, as connections are not made as a restricted role,
+    /// so creating restricted schemas is not supported and fails instead.
+    #[must_use]
+    pub fn privileged_role(self, value: bool) -> Self {
+        Self {
+            privileged_role_flag: value,
+            ..self
+        }
+    }
+
+    /// Prefix names of created schemas and roles with the given value instead of `db_pool`
+    ///
+    /// Backends with different prefixes neither see nor drop each other's schemas.
+    /// # Panics
+    /// Panics if the prefix is not lowercase ``[a-z0-9_]`` starting with a letter or underscore,
+    /// or is longer than 17 characters, which would get names truncated by Postgres.
+    #[must_use]
+    pub fn schema_name_prefix(self, value: String) -> Self {
+        assert!(
+            is_valid_db_name_prefix(value.as_str()),
+            "invalid schema name prefix {value:?}"
+        );
+        Self {
+            schema_name_prefix: value,
+            ..self
+        }
+    }
+
+    fn get_schema_name(&self, db_id: Uuid) -> String {
+        get_prefixed_db_name(self.schema_name_prefix.as_str(), db_id)
+    }
+
+    fn schema_connection_url(&self, schema_name: &str) -> String {
+        // Connect as attached role unless connecting as privileged role
+        if self.privileged_role_flag {
+            self.connection_url(
+                self.privileged_config.username.as_str(),
+                self.privileged_config.password.as_deref(),
+                schema_name,
+            )
+        } else {
+            self.connection_url(schema_name, Some(schema_name), schema_name)
+        }
+    }
+
+    fn drop_schema_statements(&self, schema_name: &str) -> Vec<String> {
+        // Drop schema with its entities, then its attached role if any
+        let mut stmts = vec![postgres::drop_schema(schema_name)];
+        if !self.privileged_role_flag {
+            stmts.push(postgres::drop_role_if_exists(schema_name));
+        }
+        stmts
+    }
+
+    fn connection_url(&self, role: &str, password: Option<&str>, schema_name: &str) -> String {
+        self.privileged_config
+            .restricted_connection_url(&RestrictedConnectionParams {
+                database: self.database.as_str(),
+                role,
+                password,
+                search_path: Some(schema_name),
+            })
+    }
+}
+
+type BError<BuildError, PoolError> = BackendError<BuildError, PoolError, ConnectionError, Error>;
+
+#[async_trait]
+impl<P: DieselPoolAssociation<AsyncPgConnection>> Backend for DieselAsyncSchemaPostgresBackend<P> {
+    type Pool = P::Pool;
+    type Connection = AsyncPgConnection;
+
+    type BuildError = P::BuildError;
+    type PoolError = P::PoolError;
+    type ConnectionError = ConnectionError;
+    type QueryError = Error;
+
+    async fn init(&self) -> Result<(), BError<P::BuildError, P::PoolError>> {
+        // Drop previous schemas if needed
+        if self.drop_previous_schemas_flag {
+            let conn = &mut P::get_connection(&self.default_pool).await.map_err(|err| {
+                BackendError::OperationPool(Operation::DropPreviousDatabases, err)
+            })?;
+
+            // Get previous schema names, skipping schemas with other prefixes
+            let schema_names = sql_query(postgres::GET_SCHEMA_NAMES_WITH_PREFIX)
+                .bind::<Text, _>(format!("{}_", self.schema_name_prefix))
+                .load::<SchemaName>(conn)
+                .await?
+                .into_iter()
+                .map(|SchemaName { schema_name }| schema_name)
+                .filter(|schema_name| {
+                    is_prefixed_db_name(self.schema_name_prefix.as_str(), schema_name)
+                });
+
+            for schema_name in schema_names {
+                conn.batch_execute(
+                    self.drop_schema_statements(schema_name.as_str())
+                        .join(";")
+                        .as_str(),
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn create(
+        &self,
+        db_id: Uuid,
+        restrict_privileges: bool,
+    ) -> Result<P::Pool, BError<P::BuildError, P::PoolError>> {
+        // Get schema name based on UUID
+        let schema_name = self.get_schema_name(db_id);
+        let schema_name = schema_name.as_str();
+
+        // Reject restricted schema if connecting as privileged role, which cannot be restricted
+        if self.privileged_role_flag && restrict_privileges {
+            return Err(BackendError::Unsupported(Operation::Create(
+                schema_name.to_owned(),
+            )));
+        }
+
+        // Get connection to shared database as privileged user
+        let conn = &mut P::get_connection(&self.default_pool).await.map_err(|err| {
+            BackendError::OperationPool(Operation::Create(schema_name.to_owned()), err)
+        })?;
+
+        // Create schema only if connecting as privileged role
+        if self.privileged_role_flag {
+            sql_query(postgres::create_schema(schema_name))
+                .execute(conn)
+                .await?;
+
+            // Create entities as privileged role
+            let entity_conn =
+                AsyncPgConnection::establish(self.schema_connection_url(schema_name).as_str())
+                    .await?;
+            (self.create_entities)(entity_conn).await;
+
+            return self.rebuild_pool(db_id).await;
+        }
+
+        // Create schema and role attached to it
+        conn.batch_execute(
+            [
+                postgres::create_schema(schema_name),
+                postgres::create_role(schema_name, schema_name, None),
+            ]
+            .join(";")
+            .as_str(),
+        )
+        .await?;
+
+        if restrict_privileges {
+            // Create entities as privileged user
+            let entity_conn = AsyncPgConnection::establish(
+                self.connection_url(
+                    self.privileged_config.username.as_str(),
+                    self.privileged_config.password.as_deref(),
+                    schema_name,
+                )
+                .as_str(),
+            )
+            .await?;
+            (self.create_entities)(entity_conn).await;
+
+            // Grant table privileges to restricted role
+            conn.batch_execute(
+                [
+                    postgres::grant_schema_usage(schema_name, schema_name),
+                    postgres::grant_restricted_schema_table_privileges(schema_name, schema_name),
+                    postgres::grant_restricted_schema_sequence_privileges(schema_name, schema_name),
+                ]
+                .join(";")
+                .as_str(),
+            )
+            .await?;
+        } else {
+            // Grant schema ownership to schema-unrestricted role
+            sql_query(postgres::grant_schema_ownership(schema_name, schema_name))
+                .execute(conn)
+                .await?;
+
+            // Create entities as schema-unrestricted role
+            let entity_conn = AsyncPgConnection::establish(
+                self.connection_url(schema_name, Some(schema_name), schema_name)
+                    .as_str(),
+            )
+            .await?;
+            (self.create_entities)(entity_conn).await;
+        }
+
+        // Create connection pool with attached role
+        self.rebuild_pool(db_id).await
+    }
+
+    async fn clean(&self, db_id: Uuid) -> Result<(), BError<P::BuildError, P::PoolError>> {
+        let schema_name = self.get_schema_name(db_id);
+        let conn = &mut P::get_connection(&self.default_pool).await.map_err(|err| {
+            BackendError::OperationPool(Operation::Clean(schema_name.clone()), err)
+        })?;
+
+        let table_names = sql_query(postgres::get_table_names(schema_name.as_str()))
+            .load::<TableName>(conn)
+            .await?
+            .into_iter()
+            .map(|TableName { table_name }| format!("{schema_name}.{table_name}"))
+            .collect::<Vec<_>>();
+
+        let table_names = table_names.iter().map(String::as_str).collect::<Vec<_>>();
+        self.clean_tables(db_id, table_names.as_slice()).await
+    }
+
+    async fn clean_tables(
+        &self,
+        db_id: Uuid,
+        table_names: &[&str],
+    ) -> Result<(), BError<P::BuildError, P::PoolError>> {
+        if table_names.is_empty() {
+            return Ok(());
+        }
+
+        let schema_name = self.get_schema_name(db_id);
+        let conn = &mut P::get_connection(&self.default_pool).await.map_err(|err| {
+            BackendError::OperationPool(Operation::Clean(schema_name.clone()), err)
+        })?;
+
+        // Get existing table names in schema
+        let existing_table_names = sql_query(postgres::get_table_names(schema_name.as_str()))
+            .load::<TableName>(conn)
+            .await?
+            .into_iter()
+            .map(|TableName { table_name }| table_name)
+            .collect::<Vec<_>>();
+
+        // Qualify given tables with schema, rejecting tables outside of it
+        let mut stmts = Vec::with_capacity(table_names.len());
+        for table_name in table_names {
+            let existing_table_name = existing_table_names
+                .iter()
+                .find(|name| matches_table_name(table_name, schema_name.as_str(), name))
+                .ok_or_else(|| BackendError::UnknownTable((*table_name).to_owned()))?;
+            stmts.push(postgres::truncate_table(
+                schema_name.as_str(),
+                existing_table_name,
+            ));
+        }
+        conn.batch_execute(stmts.join(";").as_str()).await?;
+
+        Ok(())
+    }
+
+    async fn reset_sequences(
+        &self,
+        db_id: Uuid,
+    ) -> Result<(), BError<P::BuildError, P::PoolError>> {
+        let schema_name = self.get_schema_name(db_id);
+        let conn = &mut P::get_connection(&self.default_pool).await.map_err(|err| {
+            BackendError::OperationPool(Operation::Clean(schema_name.clone()), err)
+        })?;
+
+        let stmts = sql_query(postgres::get_sequence_names(schema_name.as_str()))
+            .load::<SequenceName>(conn)
+            .await?
+            .into_iter()
+            .map(|SequenceName { sequence_name }| {
+                postgres::restart_sequence(schema_name.as_str(), sequence_name.as_str())
+            })
+            .collect::<Vec<_>>();
+
+        if !stmts.is_empty() {
+            conn.batch_execute(stmts.join(";").as_str()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn rebuild_pool(
+        &self,
+        db_id: Uuid,
+    ) -> Result<P::Pool, BError<P::BuildError, P::PoolError>> {
+        let schema_name = self.get_schema_name(db_id);
+        let schema_name = schema_name.as_str();
+        let database_url = self.schema_connection_url(schema_name);
+
+        let manager = || AsyncDieselConnectionManager::new(database_url.clone());
+
+        // Both create and build functions take manager value as a parameter,
+        // but only one should actually use it (depends on the particular connection pool API)
+        let builder = (self.create_restricted_pool)(manager());
+
+        P::build_pool(builder, manager()).await.map_err(Into::into)
+    }
+
+    async fn drop(
+        &self,
+        db_id: Uuid,
+        _is_restricted: bool,
+    ) -> Result<(), BError<P::BuildError, P::PoolError>> {
+        let schema_name = self.get_schema_name(db_id);
+        let schema_name = schema_name.as_str();
+
+        let conn = &mut P::get_connection(&self.default_pool).await.map_err(|err| {
+            BackendError::OperationPool(Operation::Drop(schema_name.to_owned()), err)
+        })?;
+
+        conn.batch_execute(self.drop_schema_statements(schema_name).join(";").as_str())
+            .await?;
+
+        Ok(())
+    }
+
+    fn get_database_name(&self, db_id: Uuid) -> String {
+        self.get_schema_name(db_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use bb8::Pool;
+    use diesel::{
+        QueryDsl,
+        dsl::{exists, sql},
+        prelude::*,
+        select, sql_query,
+        sql_types::Bool,
+        table,
+    };
+    use diesel_async::{AsyncPgConnection, RunQueryDsl, SimpleAsyncConnection};
+    use dotenvy::dotenv;
+    use tokio_shared_rt::test;
+    use uuid::Uuid;
+
+    use crate::{
+        r#async::backend::{
+            common::pool::diesel::bb8::DieselBb8,
+            error::{Error as BackendError, Operation},
+            r#trait::Backend,
+        },
+        common::{
+            config::PrivilegedPostgresConfig,
+            statement::postgres::tests::{CREATE_ENTITIES_STATEMENTS, INSERT_BOOK},
+        },
+        util::get_db_name,
+    };
+
+    use super::{super::r#trait::tests::PgDropLock, DieselAsyncSchemaPostgresBackend};
+
+    table! {
+        book (id) {
+            id -> Int4,
+            title -> Text
+        }
+    }
+
+    table! {
+        information_schema.schemata (schema_name) {
+            schema_name -> Text
+        }
+    }
+
+    async fn create_backend() -> DieselAsyncSchemaPostgresBackend<DieselBb8> {
+        dotenv().ok();
+
+        let config = PrivilegedPostgresConfig::from_env().unwrap();
+
+        DieselAsyncSchemaPostgresBackend::new(
+            config,
+            "postgres".to_owned(),
+            |_| Pool::builder(),
+            |_| Pool::builder(),
+            |mut conn| {
+                Box::pin(async move {
+                    let query = CREATE_ENTITIES_STATEMENTS.join(";");
+                    conn.batch_execute(query.as_str()).await.unwrap();
+                    Some(conn)
+                })
+            },
+        )
+        .await
+        .unwrap()
+        .drop_previous_schemas(false)
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_isolates_schemas() {
+        let backend = create_backend().await;
+
+        async {
+            backend.init().await.unwrap();
+
+            let (db_id, other_db_id) = (Uuid::new_v4(), Uuid::new_v4());
+            let conn_pool = backend.create(db_id, true).await.unwrap();
+            let other_conn_pool = backend.create(other_db_id, false).await.unwrap();
+            let conn = &mut conn_pool.get().await.unwrap();
+            let other_conn = &mut other_conn_pool.get().await.unwrap();
+
+            // book must be visible only within its own schema
+            sql_query(INSERT_BOOK).execute(conn).await.unwrap();
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
+            assert_eq!(
+                book::table
+                    .count()
+                    .get_result::<i64>(other_conn)
+                    .await
+                    .unwrap(),
+                0
+            );
+
+            // cleaning must empty tables of its own schema only
+            sql_query(INSERT_BOOK).execute(other_conn).await.unwrap();
+            backend.clean(db_id).await.unwrap();
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
+            assert_eq!(
+                book::table
+                    .count()
+                    .get_result::<i64>(other_conn)
+                    .await
+                    .unwrap(),
+                1
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_isolates_schemas_as_privileged_role() {
+        let backend = create_backend().await.privileged_role(true);
+
+        async {
+            backend.init().await.unwrap();
+
+            let (db_id, other_db_id) = (Uuid::new_v4(), Uuid::new_v4());
+            let conn_pool = backend.create(db_id, false).await.unwrap();
+            let other_conn_pool = backend.create(other_db_id, false).await.unwrap();
+            let conn = &mut conn_pool.get().await.unwrap();
+            let other_conn = &mut other_conn_pool.get().await.unwrap();
+
+            // book must be visible only within its own schema
+            sql_query(INSERT_BOOK).execute(conn).await.unwrap();
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
+            assert_eq!(
+                book::table
+                    .count()
+                    .get_result::<i64>(other_conn)
+                    .await
+                    .unwrap(),
+                0
+            );
+
+            // no role must be attached to schema
+            let role_exists = select(sql::<Bool>(
+                format!(
+                    "EXISTS (SELECT FROM pg_catalog.pg_roles WHERE rolname = '{}')",
+                    get_db_name(db_id)
+                )
+                .as_str(),
+            ))
+            .get_result::<bool>(conn)
+            .await
+            .unwrap();
+            assert!(!role_exists);
+
+            backend.drop(db_id, false).await.unwrap();
+            backend.drop(other_db_id, false).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_rejects_restricted_schema_as_privileged_role() {
+        let backend = create_backend().await.privileged_role(true);
+
+        async {
+            let db_id = Uuid::new_v4();
+            let schema_name = get_db_name(db_id);
+
+            // restricted schema must be reported as unsupported without being created
+            assert!(matches!(
+                backend.create(db_id, true).await,
+                Err(BackendError::Unsupported(Operation::Create(name))) if name == schema_name
+            ));
+            let conn = &mut backend.default_pool.get().await.unwrap();
+            assert!(!schema_exists(schema_name, conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_rejects_unknown_tables_when_cleaning() {
+        let backend = create_backend().await;
+
+        async {
+            let db_id = Uuid::new_v4();
+            let conn_pool = backend.create(db_id, true).await.unwrap();
+            let conn = &mut conn_pool.get().await.unwrap();
+            sql_query(INSERT_BOOK).execute(conn).await.unwrap();
+
+            // missing tables and tables of other schemas must be reported
+            for table_name in ["missing", "public.book"] {
+                assert!(matches!(
+                    backend.clean_tables(db_id, &[table_name]).await,
+                    Err(BackendError::UnknownTable(name)) if name == table_name
+                ));
+            }
+
+            // own tables must be cleaned whether qualified or not
+            let schema_name = get_db_name(db_id);
+            let qualified_table_name = format!("{schema_name}.book");
+            for table_name in ["book", qualified_table_name.as_str()] {
+                sql_query(INSERT_BOOK).execute(conn).await.unwrap();
+                backend.clean_tables(db_id, &[table_name]).await.unwrap();
+                assert_eq!(
+                    book::table.count().get_result::<i64>(conn).await.unwrap(),
+                    0
+                );
+            }
+
+            backend.drop(db_id, true).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    async fn schema_exists(schema_name: String, conn: &mut AsyncPgConnection) -> bool {
+        select(exists(
+            schemata::table.filter(schemata::schema_name.eq(schema_name)),
+        ))
+        .get_result(conn)
+        .await
+        .unwrap()
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_schema() {
+        let backend = create_backend().await;
+
+        async {
+            backend.init().await.unwrap();
+
+            let db_id = Uuid::new_v4();
+            backend.create(db_id, true).await.unwrap();
+
+            // schema must no longer exist once dropped
+            let conn = &mut backend.default_pool.get().await.unwrap();
+            assert!(schema_exists(get_db_name(db_id), conn).await);
+            backend.drop(db_id, true).await.unwrap();
+            assert!(!schema_exists(get_db_name(db_id), conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_previous_schemas_with_own_prefix() {
+        let backend = create_backend().await;
+        let other_backend = create_backend()
+            .await
+            .schema_name_prefix("db_pool_other".to_owned());
+
+        async {
+            let (db_id, other_db_id) = (Uuid::new_v4(), Uuid::new_v4());
+            backend.create(db_id, false).await.unwrap();
+            other_backend.create(other_db_id, false).await.unwrap();
+
+            // schema must be named after own prefix
+            let other_schema_name = other_backend.get_database_name(other_db_id);
+            assert!(other_schema_name.starts_with("db_pool_other_"));
+
+            // only schemas with own prefix must be dropped
+            let other_backend = other_backend.drop_previous_schemas(true);
+            other_backend.init().await.unwrap();
+            let conn = &mut backend.default_pool.get().await.unwrap();
+            assert!(schema_exists(backend.get_database_name(db_id), conn).await);
+            assert!(!schema_exists(other_schema_name, conn).await);
+
+            backend.drop(db_id, false).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+}
@@ -0,0 +1,23 @@
+use crate::common::statement::postgres;
+
+/// Maintenance run on a reused database after cleaning it
+#[derive(Default)]
+pub enum MaintenanceMode {
+    /// No maintenance
+    #[default]
+    None,
+    /// ``ANALYZE``, refreshing planner statistics
+    Analyze,
+    /// ``VACUUM``, reclaiming storage of truncated rows
+    Vacuum,
+}
+
+impl MaintenanceMode {
+    pub(super) fn statement(&self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Analyze => Some(postgres::ANALYZE),
+            Self::Vacuum => Some(postgres::VACUUM),
+        }
+    }
+}
@@ -1,18 +1,38 @@
+mod breaker;
+mod cleanup;
 #[cfg(feature = "diesel-async-postgres")]
 mod diesel;
+#[cfg(feature = "diesel-async-postgres")]
+mod diesel_schema;
+mod maintenance;
+mod naming;
+mod observer;
+mod privileges;
+mod role;
 #[cfg(feature = "sea-orm-postgres")]
 mod sea_orm;
 #[cfg(feature = "sqlx-postgres")]
 pub mod sqlx;
+mod template;
 #[cfg(feature = "tokio-postgres")]
 mod tokio_postgres;
 mod r#trait;
+mod truncate;
 
+pub use cleanup::cleanup_orphans;
 #[cfg(feature = "diesel-async-postgres")]
 pub use diesel::DieselAsyncPostgresBackend;
+#[cfg(feature = "diesel-async-postgres")]
+pub use diesel_schema::DieselAsyncSchemaPostgresBackend;
+pub use maintenance::MaintenanceMode;
+pub use observer::{Observer, Phase};
+pub use privileges::RestrictedPrivileges;
+pub use role::RoleOptions;
 #[cfg(feature = "sea-orm-postgres")]
 pub use sea_orm::SeaORMPostgresBackend;
 #[cfg(feature = "sqlx-postgres")]
 pub use sqlx::SqlxPostgresBackend;
+pub use template::{DatabaseLocale, DatabaseTemplate};
 #[cfg(feature = "tokio-postgres")]
 pub use tokio_postgres::TokioPostgresBackend;
+pub use truncate::{FkHandling, TruncateMode};
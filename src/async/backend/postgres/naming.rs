@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::util::is_valid_generated_db_name;
+
+type GenerateName = dyn Fn() -> String + Send + Sync + 'static;
+
+/// Generator of database names, remembering the name generated for each database
+pub(super) struct DbNameGenerator {
+    generate: Box<GenerateName>,
+    names: Mutex<HashMap<Uuid, String>>,
+}
+
+impl DbNameGenerator {
+    pub(super) fn new(generate: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        Self {
+            generate: Box::new(generate),
+            names: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Generates the name of the given database upon first use, returning it
+    ///
+    /// Fails with the generated name if it is not the prefix followed by an underscore and a valid suffix,
+    /// without remembering it.
+    pub(super) fn generate(&self, prefix: &str, db_id: Uuid) -> Result<String, String> {
+        let mut names = self.names.lock();
+        if let Some(name) = names.get(&db_id) {
+            return Ok(name.clone());
+        }
+        let name = (self.generate)();
+        if !is_valid_generated_db_name(prefix, name.as_str()) {
+            return Err(name);
+        }
+        names.insert(db_id, name.clone());
+        Ok(name)
+    }
+
+    /// Returns the name generated for the given database if any
+    pub(super) fn name(&self, db_id: Uuid) -> Option<String> {
+        self.names.lock().get(&db_id).cloned()
+    }
+
+    /// Forgets the name of the given database once it has been dropped
+    pub(super) fn forget(&self, db_id: Uuid) {
+        self.names.lock().remove(&db_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use uuid::Uuid;
+
+    use super::DbNameGenerator;
+
+    #[test]
+    fn generates_name_once_per_database() {
+        let counter = AtomicUsize::new(0);
+        let generator = DbNameGenerator::new(move || {
+            format!(
+                "db_pool_test_{}",
+                counter.fetch_add(1, Ordering::Relaxed) + 1
+            )
+        });
+        let (db_id_1, db_id_2) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert_eq!(
+            generator.generate("db_pool", db_id_1),
+            Ok("db_pool_test_1".to_owned())
+        );
+        assert_eq!(
+            generator.generate("db_pool", db_id_2),
+            Ok("db_pool_test_2".to_owned())
+        );
+        assert_eq!(
+            generator.generate("db_pool", db_id_1),
+            Ok("db_pool_test_1".to_owned())
+        );
+        assert_eq!(generator.name(db_id_2), Some("db_pool_test_2".to_owned()));
+
+        // forgotten database must get a new name
+        generator.forget(db_id_1);
+        assert_eq!(generator.name(db_id_1), None);
+        assert_eq!(
+            generator.generate("db_pool", db_id_1),
+            Ok("db_pool_test_3".to_owned())
+        );
+    }
+
+    #[test]
+    fn rejects_name_without_prefix() {
+        let generator = DbNameGenerator::new(|| "other_test_1".to_owned());
+        let db_id = Uuid::new_v4();
+
+        // invalid name must be returned without being remembered
+        assert_eq!(
+            generator.generate("db_pool", db_id),
+            Err("other_test_1".to_owned())
+        );
+        assert_eq!(generator.name(db_id), None);
+    }
+}
@@ -0,0 +1,32 @@
+use std::{fmt::Debug, time::Duration};
+
+use uuid::Uuid;
+
+/// Lifecycle phase of a database reported to an [`Observer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Creating a database
+    Create,
+    /// Cleaning a database
+    Clean,
+    /// Dropping a database
+    Drop,
+}
+
+/// Observer of database lifecycle events, e.g. to record provisioning metrics
+///
+/// All callbacks do nothing by default.
+#[allow(unused_variables)]
+pub trait Observer: Send + Sync {
+    /// Called after a database has been created
+    fn on_create(&self, db_id: Uuid, duration: Duration) {}
+
+    /// Called after a database has been cleaned
+    fn on_clean(&self, db_id: Uuid, duration: Duration) {}
+
+    /// Called after a database has been dropped
+    fn on_drop(&self, db_id: Uuid, duration: Duration) {}
+
+    /// Called when a lifecycle phase fails
+    fn on_error(&self, phase: Phase, error: &dyn Debug) {}
+}
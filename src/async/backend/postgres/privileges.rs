@@ -0,0 +1,244 @@
+use crate::common::statement::postgres;
+
+/// Privileges granted to the restricted role of created databases
+///
+/// By default, ``SELECT``, ``INSERT``, ``UPDATE``, and ``DELETE`` are granted on all tables
+/// and ``USAGE`` on all sequences.
+/// Privileges are granted in the ``public`` schema,
+/// or in every schema configured to be cleaned along with ``USAGE`` on the schema itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct RestrictedPrivileges {
+    select: bool,
+    insert: bool,
+    update: bool,
+    delete: bool,
+    truncate: bool,
+    sequence_usage: bool,
+    temporary: bool,
+}
+
+impl Default for RestrictedPrivileges {
+    fn default() -> Self {
+        Self {
+            select: true,
+            insert: true,
+            update: true,
+            delete: true,
+            truncate: false,
+            sequence_usage: true,
+            temporary: false,
+        }
+    }
+}
+
+impl RestrictedPrivileges {
+    /// Creates privileges granting nothing, to be extended with the builder methods
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            select: false,
+            insert: false,
+            update: false,
+            delete: false,
+            truncate: false,
+            sequence_usage: false,
+            temporary: false,
+        }
+    }
+
+    /// Creates privileges for reading only, granting ``SELECT`` on all tables
+    /// and ``USAGE`` on all sequences
+    #[must_use]
+    pub fn read_only() -> Self {
+        Self::none().select(true).sequence_usage(true)
+    }
+
+    /// Grant ``SELECT`` on all tables
+    #[must_use]
+    pub fn select(self, value: bool) -> Self {
+        Self {
+            select: value,
+            ..self
+        }
+    }
+
+    /// Grant ``INSERT`` on all tables
+    #[must_use]
+    pub fn insert(self, value: bool) -> Self {
+        Self {
+            insert: value,
+            ..self
+        }
+    }
+
+    /// Grant ``UPDATE`` on all tables
+    #[must_use]
+    pub fn update(self, value: bool) -> Self {
+        Self {
+            update: value,
+            ..self
+        }
+    }
+
+    /// Grant ``DELETE`` on all tables
+    #[must_use]
+    pub fn delete(self, value: bool) -> Self {
+        Self {
+            delete: value,
+            ..self
+        }
+    }
+
+    /// Grant ``TRUNCATE`` on all tables
+    #[must_use]
+    pub fn truncate(self, value: bool) -> Self {
+        Self {
+            truncate: value,
+            ..self
+        }
+    }
+
+    /// Grant ``USAGE`` on all sequences, which is needed to insert rows with generated identities
+    #[must_use]
+    pub fn sequence_usage(self, value: bool) -> Self {
+        Self {
+            sequence_usage: value,
+            ..self
+        }
+    }
+
+    /// Grant ``TEMPORARY`` on the database to allow creating temporary tables
+    #[must_use]
+    pub fn temporary(self, value: bool) -> Self {
+        Self {
+            temporary: value,
+            ..self
+        }
+    }
+
+    pub(super) fn grant_statements(
+        &self,
+        db_name: &str,
+        role_name: &str,
+        schema_names: &[String],
+    ) -> Vec<String> {
+        let table_privileges = [
+            (self.select, "SELECT"),
+            (self.insert, "INSERT"),
+            (self.update, "UPDATE"),
+            (self.delete, "DELETE"),
+            (self.truncate, "TRUNCATE"),
+        ]
+        .into_iter()
+        .filter_map(|(granted, privilege)| granted.then_some(privilege))
+        .collect::<Vec<_>>();
+
+        let mut stmts = Vec::new();
+        if schema_names.is_empty() {
+            // Grant privileges in public schema only unless schemas are configured
+            if !table_privileges.is_empty() {
+                stmts.push(postgres::grant_table_privileges(
+                    table_privileges.as_slice(),
+                    "public",
+                    role_name,
+                ));
+            }
+            if self.sequence_usage {
+                stmts.push(postgres::grant_restricted_sequence_privileges(role_name));
+            }
+        } else {
+            // Grant privileges in every configured schema, along with access to the schema itself
+            for schema_name in schema_names {
+                stmts.push(postgres::grant_schema_usage(schema_name, role_name));
+                if !table_privileges.is_empty() {
+                    stmts.push(postgres::grant_table_privileges(
+                        table_privileges.as_slice(),
+                        schema_name,
+                        role_name,
+                    ));
+                }
+                if self.sequence_usage {
+                    stmts.push(postgres::grant_restricted_schema_sequence_privileges(
+                        schema_name,
+                        role_name,
+                    ));
+                }
+            }
+        }
+        if self.temporary {
+            stmts.push(postgres::grant_temporary_privilege(db_name, role_name));
+        }
+        stmts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RestrictedPrivileges;
+
+    #[test]
+    fn default_matches_fixed_privileges() {
+        assert_eq!(
+            RestrictedPrivileges::default().grant_statements("db_pool_role", "db_pool_role", &[]),
+            vec![
+                "GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA public TO db_pool_role",
+                "GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA public TO db_pool_role",
+            ]
+        );
+    }
+
+    #[test]
+    fn grants_selected_privileges() {
+        assert_eq!(
+            RestrictedPrivileges::none()
+                .select(true)
+                .truncate(true)
+                .temporary(true)
+                .grant_statements("db_pool_role", "db_pool_role", &[]),
+            vec![
+                "GRANT SELECT, TRUNCATE ON ALL TABLES IN SCHEMA public TO db_pool_role",
+                "GRANT TEMPORARY ON DATABASE db_pool_role TO db_pool_role",
+            ]
+        );
+    }
+
+    #[test]
+    fn read_only_grants_select_and_sequence_usage() {
+        assert_eq!(
+            RestrictedPrivileges::read_only().grant_statements("db_pool_role", "db_pool_role", &[]),
+            vec![
+                "GRANT SELECT ON ALL TABLES IN SCHEMA public TO db_pool_role",
+                "GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA public TO db_pool_role",
+            ]
+        );
+    }
+
+    #[test]
+    fn grants_nothing() {
+        assert!(
+            RestrictedPrivileges::none()
+                .grant_statements("db_pool_role", "db_pool_role", &[])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn grants_privileges_in_configured_schemas() {
+        assert_eq!(
+            RestrictedPrivileges::read_only().grant_statements(
+                "db_pool_role",
+                "db_pool_role",
+                &["public".to_owned(), "billing".to_owned()]
+            ),
+            vec![
+                "GRANT USAGE ON SCHEMA public TO db_pool_role",
+                "GRANT SELECT ON ALL TABLES IN SCHEMA public TO db_pool_role",
+                "GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA public TO db_pool_role",
+                "GRANT USAGE ON SCHEMA billing TO db_pool_role",
+                "GRANT SELECT ON ALL TABLES IN SCHEMA billing TO db_pool_role",
+                "GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA billing TO db_pool_role",
+            ]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::SystemTime;
+
+use crate::common::statement::postgres;
+
+/// Attributes of the role attached to created databases
+///
+/// By default, the role can log in without a connection limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleOptions {
+    login: bool,
+    connection_limit: Option<u32>,
+}
+
+impl Default for RoleOptions {
+    fn default() -> Self {
+        Self {
+            login: true,
+            connection_limit: None,
+        }
+    }
+}
+
+impl RoleOptions {
+    /// Allow the role to log in, disabling it (``NOLOGIN``) for group-role scenarios
+    /// in which nothing connects as the role itself
+    #[must_use]
+    pub fn login(self, value: bool) -> Self {
+        Self {
+            login: value,
+            ..self
+        }
+    }
+
+    /// Limit the number of concurrent connections of the role (``CONNECTION LIMIT``)
+    ///
+    /// Connection pools connecting as the role count towards the limit.
+    #[must_use]
+    pub fn connection_limit(self, value: u32) -> Self {
+        Self {
+            connection_limit: Some(value),
+            ..self
+        }
+    }
+
+    pub(super) fn create_statement(
+        &self,
+        name: &str,
+        password: &str,
+        valid_until: Option<SystemTime>,
+    ) -> String {
+        postgres::create_role_with_attributes(
+            name,
+            password,
+            valid_until,
+            self.login,
+            self.connection_limit,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RoleOptions;
+
+    #[test]
+    fn default_matches_fixed_attributes() {
+        assert_eq!(
+            RoleOptions::default().create_statement("db_pool_role", "db_pool_role", None),
+            "CREATE ROLE db_pool_role WITH LOGIN PASSWORD 'db_pool_role'"
+        );
+    }
+
+    #[test]
+    fn applies_configured_attributes() {
+        assert_eq!(
+            RoleOptions::default()
+                .login(false)
+                .connection_limit(1)
+                .create_statement("db_pool_role", "db_pool_role", None),
+            "CREATE ROLE db_pool_role WITH NOLOGIN PASSWORD 'db_pool_role' CONNECTION LIMIT 1"
+        );
+    }
+}
@@ -1,16 +1,24 @@
-use std::{borrow::Cow, collections::HashMap, pin::Pin};
+use std::{borrow::Cow, collections::HashMap, pin::Pin, time::Duration};
 
 use async_trait::async_trait;
-use futures::Future;
+use futures::{Future, future::try_join_all};
 use parking_lot::Mutex;
 use sea_orm::{
     ActiveModelBehavior, ColumnTrait, ConnectOptions, ConnectionTrait, Database,
-    DatabaseConnection, DbErr, DeriveEntityModel, DerivePrimaryKey, DeriveRelation, EntityTrait,
-    EnumIter, FromQueryResult, PrimaryKeyTrait, QueryFilter, QuerySelect,
+    DatabaseConnection, DbBackend, DbErr, DeriveEntityModel, DerivePrimaryKey, DeriveRelation,
+    EntityTrait, EnumIter, FromQueryResult, PrimaryKeyTrait, QueryFilter, QuerySelect, RuntimeErr,
+    Statement,
 };
+use tokio::sync::Mutex as AsyncMutex;
 use uuid::Uuid;
 
-use crate::{common::config::PrivilegedPostgresConfig, util::get_db_name};
+use crate::{
+    common::{
+        config::{PrivilegedPostgresConfig, postgres::RestrictedConnectionParams},
+        statement::postgres,
+    },
+    util::{DEFAULT_DB_NAME_PREFIX, is_valid_db_name_prefix},
+};
 
 use super::{
     super::{
@@ -21,22 +29,115 @@ use super::{
         error::Error as BackendError,
         r#trait::Backend,
     },
-    r#trait::{PostgresBackend, PostgresBackendWrapper},
+    breaker::CircuitBreaker,
+    maintenance::MaintenanceMode,
+    naming::DbNameGenerator,
+    observer::Observer,
+    privileges::RestrictedPrivileges,
+    role::RoleOptions,
+    template::{DatabaseLocale, DatabaseTemplate},
+    r#trait::{PostgresBackend, PostgresBackendWrapper, RoleAttributes},
+    truncate::{CleanTable, FkHandling, TruncateMode},
 };
 
-type CreateEntities = dyn Fn(DatabaseConnection) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+type CreateEntitiesFuture = Pin<Box<dyn Future<Output = Result<(), DbErr>> + Send + 'static>>;
+
+type CreateEntities =
+    dyn Fn(Uuid, String, DatabaseConnection) -> CreateEntitiesFuture + Send + Sync + 'static;
+
+// Adapt infallible stage creating entities to fallible one
+fn infallible_stage(
+    create_entities: impl Fn(
+        Uuid,
+        String,
+        DatabaseConnection,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+    + Send
+    + Sync
+    + 'static,
+) -> Box<CreateEntities> {
+    Box::new(move |db_id, db_name, conn| -> CreateEntitiesFuture {
+        let create_entities = create_entities(db_id, db_name, conn);
+        Box::pin(async move {
+            create_entities.await;
+            Ok(())
+        })
+    })
+}
+
+type CreateEntitiesWithPool<Pool> = dyn Fn(Uuid, String, Pool) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+    + Send
+    + Sync
+    + 'static;
+
+type BeforeDrop = dyn Fn(Uuid, String, DatabaseConnection) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+    + Send
+    + Sync
+    + 'static;
+
+type OnInit = dyn Fn(DatabaseConnection) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
     + Send
     + Sync
     + 'static;
 
 /// [`SeaORM Postgres`](https://docs.rs/sea-orm/1.1.12/sea_orm/type.DbBackend.html#variant.Postgres) backend
+///
+/// Creating entities in a transaction and setting a lock timeout when dropping databases
+/// are not supported, since statements run over a connection pool are not guaranteed to share a connection.
+#[allow(clippy::struct_excessive_bools)]
 pub struct SeaORMPostgresBackend {
     privileged_config: PrivilegedPostgresConfig,
     default_pool: DatabaseConnection,
     db_conns: Mutex<HashMap<Uuid, DatabaseConnection>>,
     create_restricted_pool: Box<dyn for<'tmp> Fn(&'tmp mut ConnectOptions) + Send + Sync + 'static>,
-    create_entities: Box<CreateEntities>,
+    create_entities: Vec<Box<CreateEntities>>,
+    create_entities_with_pool: Option<Box<CreateEntitiesWithPool<DatabaseConnection>>>,
     drop_previous_databases_flag: bool,
+    analyze_after_create_flag: bool,
+    max_create_retries: usize,
+    role_validity: Option<Duration>,
+    before_drop: Option<Box<BeforeDrop>>,
+    on_init: Option<Box<OnInit>>,
+    drop_previous_databases_concurrency: Option<usize>,
+    drop_grace_period: Option<Duration>,
+    restricted_min_idle: Option<u32>,
+    database_template: DatabaseTemplate,
+    owner_config: Option<PrivilegedPostgresConfig>,
+    excluded_tables: Vec<String>,
+    entity_template_id: Option<Uuid>,
+    verify_query: Option<String>,
+    db_name_prefix: String,
+    connection_retries: Option<(usize, Duration)>,
+    restricted_privileges: RestrictedPrivileges,
+    drop_timeout: Option<Duration>,
+    initial_size: usize,
+    keep_databases_on_panic_flag: bool,
+    extensions: Vec<String>,
+    maintenance_mode: MaintenanceMode,
+    clean_batch_size: usize,
+    truncate_mode: TruncateMode,
+    dry_run_flag: bool,
+    restricted_password: Option<Box<dyn Fn(Uuid) -> String + Send + Sync + 'static>>,
+    reassign_owned_roles: Vec<String>,
+    clean_schemas: Vec<String>,
+    fk_handling: FkHandling,
+    observer: Option<Box<dyn Observer>>,
+    warn_previous_databases_flag: bool,
+    statement_timeout: Option<Duration>,
+    unrestricted_owner_role: Option<String>,
+    terminate_connections_flag: bool,
+    connection_statements: Vec<String>,
+    db_name_generator: Option<DbNameGenerator>,
+    truncate_partition_parents_only_flag: bool,
+    previous_databases_ttl: Option<Duration>,
+    warm_up_pools_flag: bool,
+    clean_table: Option<Box<CleanTable>>,
+    create_jitter: Option<Duration>,
+    role_creation_lock: Option<AsyncMutex<()>>,
+    database_locale: DatabaseLocale,
+    role_options: RoleOptions,
+    shared_role: Option<String>,
+    default_pool_breaker: Option<CircuitBreaker>,
 }
 
 impl SeaORMPostgresBackend {
@@ -99,8 +200,54 @@ impl SeaORMPostgresBackend {
             default_pool,
             db_conns: Mutex::new(HashMap::new()),
             create_restricted_pool: Box::new(create_restricted_pool),
-            create_entities: Box::new(create_entities),
+            create_entities: vec![infallible_stage(move |_, _, conn| create_entities(conn))],
+            create_entities_with_pool: None,
             drop_previous_databases_flag: true,
+            analyze_after_create_flag: false,
+            max_create_retries: 3,
+            role_validity: None,
+            before_drop: None,
+            on_init: None,
+            drop_previous_databases_concurrency: None,
+            drop_grace_period: None,
+            restricted_min_idle: None,
+            database_template: DatabaseTemplate::Default,
+            owner_config: None,
+            excluded_tables: Vec::new(),
+            entity_template_id: None,
+            verify_query: None,
+            db_name_prefix: DEFAULT_DB_NAME_PREFIX.to_owned(),
+            connection_retries: None,
+            restricted_privileges: RestrictedPrivileges::default(),
+            drop_timeout: None,
+            initial_size: 0,
+            keep_databases_on_panic_flag: false,
+            extensions: Vec::new(),
+            maintenance_mode: MaintenanceMode::None,
+            clean_batch_size: 0,
+            truncate_mode: TruncateMode::PerTable,
+            dry_run_flag: false,
+            restricted_password: None,
+            reassign_owned_roles: Vec::new(),
+            clean_schemas: Vec::new(),
+            fk_handling: FkHandling::Cascade,
+            observer: None,
+            warn_previous_databases_flag: false,
+            statement_timeout: None,
+            unrestricted_owner_role: None,
+            terminate_connections_flag: false,
+            connection_statements: Vec::new(),
+            db_name_generator: None,
+            truncate_partition_parents_only_flag: false,
+            previous_databases_ttl: None,
+            warm_up_pools_flag: false,
+            clean_table: None,
+            create_jitter: None,
+            role_creation_lock: None,
+            database_locale: DatabaseLocale::default(),
+            role_options: RoleOptions::default(),
+            shared_role: None,
+            default_pool_breaker: None,
         })
     }
 
@@ -112,332 +259,1992 @@ impl SeaORMPostgresBackend {
             ..self
         }
     }
-}
 
-#[async_trait]
-impl<'pool> PostgresBackend<'pool> for SeaORMPostgresBackend {
-    type Connection = DatabaseConnection;
-    type PooledConnection = PooledConnection;
-    type Pool = DatabaseConnection;
+    /// Run ``ANALYZE`` on created databases once entities are created
+    #[must_use]
+    pub fn analyze_after_create(self, value: bool) -> Self {
+        Self {
+            analyze_after_create_flag: value,
+            ..self
+        }
+    }
 
-    type BuildError = BuildError;
-    type PoolError = PoolError;
-    type ConnectionError = ConnectionError;
-    type QueryError = QueryError;
+    /// Retry database creation with a newly generated name up to the given number of times when the name is already taken
+    #[must_use]
+    pub fn max_create_retries(self, value: usize) -> Self {
+        Self {
+            max_create_retries: value,
+            ..self
+        }
+    }
 
-    async fn execute_query(
-        &self,
-        query: &str,
-        conn: &mut DatabaseConnection,
-    ) -> Result<(), QueryError> {
-        conn.execute_unprepared(query).await?;
-        Ok(())
+    /// Create restricted roles valid only for the given duration after database creation
+    #[must_use]
+    pub fn role_validity(self, value: Duration) -> Self {
+        Self {
+            role_validity: Some(value),
+            ..self
+        }
     }
 
-    async fn batch_execute_query<'a>(
-        &self,
-        query: impl IntoIterator<Item = Cow<'a, str>> + Send,
-        conn: &mut DatabaseConnection,
-    ) -> Result<(), QueryError> {
-        let query = query.into_iter().collect::<Vec<_>>().join(";");
-        self.execute_query(query.as_str(), conn).await
+    /// Run a hook with a privileged connection to each database right before it is dropped
+    #[must_use]
+    pub fn before_drop(
+        self,
+        hook: impl Fn(
+            Uuid,
+            String,
+            DatabaseConnection,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        Self {
+            before_drop: Some(Box::new(hook)),
+            ..self
+        }
     }
 
-    async fn get_default_connection(&'pool self) -> Result<PooledConnection, PoolError> {
-        Ok(self.default_pool.clone().into())
+    /// Run a hook once with a privileged connection to the default database upon initialization,
+    /// right after dropping previous databases
+    #[must_use]
+    pub fn on_init(
+        self,
+        hook: impl Fn(DatabaseConnection) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        Self {
+            on_init: Some(Box::new(hook)),
+            ..self
+        }
     }
 
-    async fn establish_privileged_database_connection(
-        &self,
-        db_id: Uuid,
-    ) -> Result<DatabaseConnection, ConnectionError> {
-        let db_name = get_db_name(db_id);
-        let database_url = self
-            .privileged_config
-            .privileged_database_connection_url(db_name.as_str());
-        let opts = ConnectOptions::new(database_url);
-        Database::connect(opts).await.map_err(Into::into)
+    /// Limit the number of previous databases dropped concurrently upon initialization, ``1`` dropping them serially
+    #[must_use]
+    pub fn drop_previous_databases_concurrency(self, value: usize) -> Self {
+        Self {
+            drop_previous_databases_concurrency: Some(value),
+            ..self
+        }
     }
 
-    async fn establish_restricted_database_connection(
-        &self,
-        db_id: Uuid,
-    ) -> Result<DatabaseConnection, ConnectionError> {
-        let db_name = get_db_name(db_id);
-        let db_name = db_name.as_str();
-        let database_url = self.privileged_config.restricted_database_connection_url(
-            db_name,
-            Some(db_name),
-            db_name,
+    /// Add a stage creating further entities, run in order after previously added stages
+    #[must_use]
+    pub fn then_create_entities(
+        mut self,
+        create_entities: impl Fn(
+            DatabaseConnection,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.create_entities
+            .push(infallible_stage(move |_, _, conn| create_entities(conn)));
+        self
+    }
+
+    /// Add a stage creating further entities like [`then_create_entities`](Self::then_create_entities),
+    /// additionally receiving the ID and name of the database the entities are created in
+    ///
+    /// When entities are created once, the ID and name are those of the entity template database.
+    #[must_use]
+    pub fn then_create_entities_with_context(
+        mut self,
+        create_entities: impl Fn(
+            Uuid,
+            String,
+            DatabaseConnection,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.create_entities.push(infallible_stage(create_entities));
+        self
+    }
+
+    /// Add a stage creating further entities like [`then_create_entities`](Self::then_create_entities),
+    /// failing database creation with the returned error instead of having to panic
+    #[must_use]
+    pub fn then_try_create_entities(
+        mut self,
+        create_entities: impl Fn(
+            DatabaseConnection,
+        )
+            -> Pin<Box<dyn Future<Output = Result<(), DbErr>> + Send + 'static>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.create_entities
+            .push(Box::new(move |_, _, conn| create_entities(conn)));
+        self
+    }
+
+    /// Create further entities over a connection pool to the database after all stages,
+    /// allowing them to be created concurrently over multiple connections
+    ///
+    /// The pool connects as the privileged user for restricted databases.
+    #[must_use]
+    pub fn create_entities_with_pool(
+        self,
+        create_entities: impl Fn(
+            Uuid,
+            String,
+            DatabaseConnection,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        Self {
+            create_entities_with_pool: Some(Box::new(create_entities)),
+            ..self
+        }
+    }
+
+    /// Keep retrying to drop databases still in use for the given grace period before forcing them to drop
+    #[must_use]
+    pub fn drop_grace_period(self, value: Duration) -> Self {
+        Self {
+            drop_grace_period: Some(value),
+            ..self
+        }
+    }
+
+    /// Keep at least the given number of idle connections in restricted connection pools
+    #[must_use]
+    pub fn restricted_min_idle(self, value: u32) -> Self {
+        Self {
+            restricted_min_idle: Some(value),
+            ..self
+        }
+    }
+
+    /// Create databases from the given template
+    #[must_use]
+    pub fn database_template(self, value: DatabaseTemplate) -> Self {
+        Self {
+            database_template: value,
+            ..self
+        }
+    }
+
+    /// Have the role of the given configuration own restricted databases and their entities instead of the privileged role
+    ///
+    /// The privileged role still creates and drops databases and roles and must be a member of the owner role.
+    /// Entities are created and tables are cleaned as the owner role.
+    #[must_use]
+    pub fn owner_config(self, value: PrivilegedPostgresConfig) -> Self {
+        Self {
+            owner_config: Some(value),
+            ..self
+        }
+    }
+
+    /// Exclude the given tables from cleaning, e.g. lookup tables seeded upon creation
+    /// Tables can be given by name or qualified with their schema as ``schema.table``
+    #[must_use]
+    pub fn exclude_tables_from_cleaning(self, value: Vec<String>) -> Self {
+        Self {
+            excluded_tables: value,
+            ..self
+        }
+    }
+
+    /// Create entities once in a template database upon initialization and clone it for each restricted database
+    ///
+    /// Entities are still created separately for each unrestricted database.
+    #[must_use]
+    pub fn create_entities_once(self, value: bool) -> Self {
+        Self {
+            entity_template_id: value.then(Uuid::new_v4),
+            ..self
+        }
+    }
+
+    /// Run the given query as the attached role after creating a database to verify that the database is usable
+    #[must_use]
+    pub fn verify_query(self, value: String) -> Self {
+        Self {
+            verify_query: Some(value),
+            ..self
+        }
+    }
+
+    /// Prefix names of created databases and roles with the given value instead of `db_pool`
+    ///
+    /// Backends with different prefixes neither see nor drop each other's databases.
+    /// # Panics
+    /// Panics if the prefix is not lowercase ``[a-z0-9_]`` starting with a letter or underscore,
+    /// or is longer than 17 characters, which would get names truncated by Postgres.
+    #[must_use]
+    pub fn db_name_prefix(self, value: String) -> Self {
+        assert!(
+            is_valid_db_name_prefix(value.as_str()),
+            "invalid database name prefix {value:?}"
         );
-        let opts = ConnectOptions::new(database_url);
-        Database::connect(opts).await.map_err(Into::into)
+        Self {
+            db_name_prefix: value,
+            ..self
+        }
     }
 
-    fn put_database_connection(&self, db_id: Uuid, conn: DatabaseConnection) {
-        self.db_conns.lock().insert(db_id, conn);
+    /// Retry establishing connections to a created database up to the given number of attempts,
+    /// doubling the delay between attempts starting from the given base delay
+    #[must_use]
+    pub fn connection_retries(self, attempts: usize, base_delay: Duration) -> Self {
+        Self {
+            connection_retries: Some((attempts, base_delay)),
+            ..self
+        }
     }
 
-    fn get_database_connection(&self, db_id: Uuid) -> DatabaseConnection {
-        self.db_conns
-            .lock()
-            .remove(&db_id)
-            .unwrap_or_else(|| panic!("connection map must have a connection for {db_id}"))
+    /// Grant the given privileges to the restricted role instead of the default ones
+    #[must_use]
+    pub fn restricted_privileges(self, value: RestrictedPrivileges) -> Self {
+        Self {
+            restricted_privileges: value,
+            ..self
+        }
     }
 
-    async fn get_previous_database_names(
-        &self,
-        conn: &mut DatabaseConnection,
-    ) -> Result<Vec<String>, QueryError> {
-        #[derive(Clone, Debug, DeriveEntityModel)]
-        #[sea_orm(table_name = "pg_database")]
-        pub struct Model {
-            #[sea_orm(primary_key)]
-            oid: i32,
-            datname: String,
+    /// Give up dropping a database when the pool it belongs to is dropped after the given timeout,
+    /// leaving it to be dropped as a previous database on the next initialization
+    #[must_use]
+    pub fn drop_timeout(self, value: Duration) -> Self {
+        Self {
+            drop_timeout: Some(value),
+            ..self
         }
+    }
 
-        #[derive(Debug, EnumIter, DeriveRelation)]
-        pub enum Relation {}
+    /// Eagerly create the given number of reusable databases concurrently when creating a database pool
+    #[must_use]
+    pub fn initial_size(self, value: usize) -> Self {
+        Self {
+            initial_size: value,
+            ..self
+        }
+    }
 
-        impl ActiveModelBehavior for ActiveModel {}
+    /// Keep databases pulled by a panicking thread for inspection instead of cleaning and dropping them
+    ///
+    /// Kept databases are logged with the `tracing` feature and must be dropped manually,
+    /// or as previous databases on the next initialization.
+    #[must_use]
+    pub fn keep_databases_on_panic(self, value: bool) -> Self {
+        Self {
+            keep_databases_on_panic_flag: value,
+            ..self
+        }
+    }
 
-        #[derive(FromQueryResult)]
-        struct QueryModel {
-            datname: String,
+    /// Create the given extensions as privileged user in each database before creating entities
+    #[must_use]
+    pub fn extensions(self, value: Vec<String>) -> Self {
+        Self {
+            extensions: value,
+            ..self
         }
+    }
 
-        Entity::find()
-            .select_only()
-            .column(Column::Datname)
-            .filter(Column::Datname.like("db_pool_%"))
-            .into_model::<QueryModel>()
-            .all(conn)
-            .await
-            .map(|mut models| models.drain(..).map(|model| model.datname).collect())
-            .map_err(Into::into)
+    /// Run the given maintenance on reused databases after cleaning them
+    #[must_use]
+    pub fn maintenance_mode(self, value: MaintenanceMode) -> Self {
+        Self {
+            maintenance_mode: value,
+            ..self
+        }
+    }
+
+    /// Truncate tables in batches of the given size when cleaning, or all in one batch if zero
+    #[must_use]
+    pub fn clean_batch_size(self, value: usize) -> Self {
+        Self {
+            clean_batch_size: value,
+            ..self
+        }
+    }
+
+    /// Truncate tables in the given mode when cleaning
+    #[must_use]
+    pub fn truncate_mode(self, value: TruncateMode) -> Self {
+        Self {
+            truncate_mode: value,
+            ..self
+        }
+    }
+
+    /// Log statements instead of executing them, for reviewing what would be run against the server
+    ///
+    /// Statements are only logged with the ``tracing`` feature enabled.
+    #[must_use]
+    pub fn dry_run(self, value: bool) -> Self {
+        Self {
+            dry_run_flag: value,
+            ..self
+        }
+    }
+
+    /// Generate the password of the role attached to each database from its ID instead of using the database name
+    #[must_use]
+    pub fn restricted_password(
+        self,
+        value: impl Fn(Uuid) -> String + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            restricted_password: Some(Box::new(value)),
+            ..self
+        }
+    }
+
+    /// Reassign objects owned by the given roles to the database-unrestricted role after creating entities in unrestricted databases
+    #[must_use]
+    pub fn reassign_owned(self, value: Vec<String>) -> Self {
+        Self {
+            reassign_owned_roles: value,
+            ..self
+        }
+    }
+
+    /// Restrict cleaning to tables in the given schemas, scanning all non-system schemas by default
+    ///
+    /// Restricted privileges are granted in the given schemas instead of the ``public`` schema.
+    #[must_use]
+    pub fn clean_schemas(self, value: Vec<String>) -> Self {
+        Self {
+            clean_schemas: value,
+            ..self
+        }
+    }
+
+    /// Handle foreign keys between tables in the given way when cleaning
+    #[must_use]
+    pub fn fk_handling(self, value: FkHandling) -> Self {
+        Self {
+            fk_handling: value,
+            ..self
+        }
+    }
+
+    /// Report database lifecycle events to the given observer, e.g. to record provisioning metrics
+    #[must_use]
+    pub fn observer(self, value: impl Observer + 'static) -> Self {
+        Self {
+            observer: Some(Box::new(value)),
+            ..self
+        }
+    }
+
+    /// Only log databases created in previous runs upon initialization instead of dropping them,
+    /// taking precedence over [`drop_previous_databases`](Self::drop_previous_databases)
+    #[must_use]
+    pub fn warn_previous_databases(self, value: bool) -> Self {
+        Self {
+            warn_previous_databases_flag: value,
+            ..self
+        }
+    }
+
+    /// Set `statement_timeout` on created databases, applying to every subsequent connection
+    #[must_use]
+    pub fn statement_timeout(self, value: Duration) -> Self {
+        Self {
+            statement_timeout: Some(value),
+            ..self
+        }
+    }
+
+    /// Have the given role own unrestricted databases instead of their database-unrestricted role,
+    /// which is granted membership in the owner role to keep creating entities
+    #[must_use]
+    pub fn unrestricted_database_owner(self, value: String) -> Self {
+        Self {
+            unrestricted_owner_role: Some(value),
+            ..self
+        }
+    }
+
+    /// Terminate lingering connections to databases right before dropping them
+    ///
+    /// Disabled by default since force-terminating connections can mask connection leaks.
+    #[must_use]
+    pub fn terminate_connections_before_drop(self, value: bool) -> Self {
+        Self {
+            terminate_connections_flag: value,
+            ..self
+        }
+    }
+
+    /// Run the given statements, e.g. ``SET search_path TO app, public``, on every connection
+    /// established as the database-unrestricted role
+    #[must_use]
+    pub fn connection_statements(self, value: Vec<String>) -> Self {
+        Self {
+            connection_statements: value,
+            ..self
+        }
+    }
+
+    /// Generate database names with the given function instead of deriving them from database IDs,
+    /// e.g. to correlate databases with tests
+    ///
+    /// Generated names must be the [`db_name_prefix`](Self::db_name_prefix) followed by an underscore
+    /// and lowercase ``[a-z0-9_]``, within 54 characters, creating a database failing otherwise.
+    /// Previous databases with such names are dropped upon initialization,
+    /// except for names ending in a database ID, which belong to backends with longer prefixes.
+    #[must_use]
+    pub fn db_name_generator(self, generate: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        Self {
+            db_name_generator: Some(DbNameGenerator::new(generate)),
+            ..self
+        }
+    }
+
+    /// Skip partitions when cleaning, emptying partitioned tables through their parents only
+    #[must_use]
+    pub fn truncate_partition_parents_only(self, value: bool) -> Self {
+        Self {
+            truncate_partition_parents_only_flag: value,
+            ..self
+        }
+    }
+
+    /// Drop only previous databases created longer ago than the given duration on initialization,
+    /// leaving databases in use by parallel processes intact
+    #[must_use]
+    pub fn previous_databases_ttl(self, value: Duration) -> Self {
+        Self {
+            previous_databases_ttl: Some(value),
+            ..self
+        }
+    }
+
+    /// Establish the minimum number of idle connections of restricted connection pools
+    /// before handing them out, so that first queries do not wait for connections
+    #[must_use]
+    pub fn warm_up_pools(self, value: bool) -> Self {
+        Self {
+            warm_up_pools_flag: value,
+            ..self
+        }
+    }
+
+    /// Clean tables with the statements returned for their names, e.g. to preserve seed rows,
+    /// truncating tables for which no statements are returned
+    #[must_use]
+    pub fn clean_table_with(
+        self,
+        value: impl Fn(&str) -> Option<Vec<String>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            clean_table: Some(Box::new(value)),
+            ..self
+        }
+    }
+
+    /// Delay creating each database by a random duration up to the given one,
+    /// spreading out concurrent creations
+    #[must_use]
+    pub fn create_jitter(self, value: Duration) -> Self {
+        Self {
+            create_jitter: Some(value),
+            ..self
+        }
+    }
+
+    /// Create roles one at a time, avoiding contention between concurrent creations
+    #[must_use]
+    pub fn serialize_role_creation(self, value: bool) -> Self {
+        Self {
+            role_creation_lock: value.then(AsyncMutex::default),
+            ..self
+        }
+    }
+
+    /// Create databases with the given encoding and locale instead of those of the template
+    #[must_use]
+    pub fn database_locale(self, value: DatabaseLocale) -> Self {
+        Self {
+            database_locale: value,
+            ..self
+        }
+    }
+
+    /// Create roles attached to databases with the given attributes
+    #[must_use]
+    pub fn role_options(self, value: RoleOptions) -> Self {
+        Self {
+            role_options: value,
+            ..self
+        }
+    }
+
+    /// Connect to restricted databases as the given pre-existing role instead of creating a role
+    /// attached to each database
+    ///
+    /// The role is neither created nor dropped, only being granted privileges on each database.
+    /// Its password must be provided with [`restricted_password`](Self::restricted_password).
+    #[must_use]
+    pub fn shared_role(self, value: String) -> Self {
+        Self {
+            shared_role: Some(value),
+            ..self
+        }
+    }
+
+    /// Fast-fail getting connections from the default pool for a cooldown period after the given number
+    /// of consecutive failures, sparing an overloaded server from further attempts
+    ///
+    /// A single attempt is then let through, the cooldown doubling every time it fails.
+    #[must_use]
+    pub fn default_pool_circuit_breaker(self, threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            default_pool_breaker: Some(CircuitBreaker::new(threshold, cooldown)),
+            ..self
+        }
+    }
+
+    async fn build_connection_pool(
+        &self,
+        database_url: String,
+    ) -> Result<DatabaseConnection, BuildError> {
+        let mut opts = ConnectOptions::new(database_url);
+        (self.create_restricted_pool)(&mut opts);
+        if let Some(min_idle) = self.restricted_min_idle {
+            opts.min_connections(min_idle);
+        }
+        // Defer connecting to database never created in dry run
+        if self.dry_run_flag {
+            opts.connect_lazy(true);
+        }
+        Database::connect(opts).await.map_err(Into::into)
+    }
+}
+
+#[async_trait]
+impl<'pool> PostgresBackend<'pool> for SeaORMPostgresBackend {
+    type Connection = DatabaseConnection;
+    type PooledConnection = PooledConnection;
+    type Pool = DatabaseConnection;
+
+    type BuildError = BuildError;
+    type PoolError = PoolError;
+    type ConnectionError = ConnectionError;
+    type QueryError = QueryError;
+
+    async fn execute_query(
+        &self,
+        query: &str,
+        conn: &mut DatabaseConnection,
+    ) -> Result<(), QueryError> {
+        if self.dry_run_flag {
+            #[cfg(feature = "tracing")]
+            tracing::info!(query, "skipping statement in dry run");
+            return Ok(());
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(query, "executing statement");
+        conn.execute_unprepared(query).await?;
+        Ok(())
+    }
+
+    async fn batch_execute_query<'a>(
+        &self,
+        query: impl IntoIterator<Item = Cow<'a, str>> + Send,
+        conn: &mut DatabaseConnection,
+    ) -> Result<(), QueryError> {
+        let query = query.into_iter().collect::<Vec<_>>().join(";");
+        self.execute_query(query.as_str(), conn).await
+    }
+
+    async fn get_default_connection(&'pool self) -> Result<PooledConnection, PoolError> {
+        Ok(self.default_pool.clone().into())
+    }
+
+    async fn establish_privileged_database_connection(
+        &self,
+        db_id: Uuid,
+    ) -> Result<DatabaseConnection, ConnectionError> {
+        let db_name = self.get_db_name(db_id);
+        let database_url = self
+            .owner_config
+            .as_ref()
+            .unwrap_or(&self.privileged_config)
+            .privileged_database_connection_url(db_name.as_str());
+        let opts = ConnectOptions::new(database_url);
+        Database::connect(opts).await.map_err(Into::into)
+    }
+
+    async fn establish_privileged_default_connection(
+        &self,
+    ) -> Result<DatabaseConnection, ConnectionError> {
+        let database_url = self.privileged_config.default_connection_url();
+        let opts = ConnectOptions::new(database_url);
+        Database::connect(opts).await.map_err(Into::into)
+    }
+
+    async fn establish_restricted_database_connection(
+        &self,
+        db_id: Uuid,
+    ) -> Result<DatabaseConnection, ConnectionError> {
+        let db_name = self.get_db_name(db_id);
+        let db_name = db_name.as_str();
+        let role_name = self.get_role_name(db_id);
+        let password = self.get_restricted_password(db_id);
+        let database_url =
+            self.privileged_config
+                .restricted_connection_url(&RestrictedConnectionParams {
+                    role: role_name.as_str(),
+                    password: Some(password.as_str()),
+                    ..RestrictedConnectionParams::for_database(db_name)
+                });
+        let opts = ConnectOptions::new(database_url);
+        Database::connect(opts).await.map_err(Into::into)
+    }
+
+    fn put_database_connection(&self, db_id: Uuid, conn: DatabaseConnection) {
+        self.db_conns.lock().insert(db_id, conn);
+    }
+
+    fn get_database_connection(&self, db_id: Uuid) -> DatabaseConnection {
+        self.db_conns
+            .lock()
+            .remove(&db_id)
+            .unwrap_or_else(|| panic!("connection map must have a connection for {db_id}"))
+    }
+
+    async fn get_previous_database_names(
+        &self,
+        conn: &mut DatabaseConnection,
+    ) -> Result<Vec<String>, QueryError> {
+        #[derive(FromQueryResult)]
+        struct QueryModel {
+            datname: String,
+        }
+
+        QueryModel::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            postgres::GET_DATABASE_NAMES_WITH_PREFIX,
+            [format!("{}_", self.db_name_prefix).into()],
+        ))
+        .all(conn)
+        .await
+        .map(|models| models.into_iter().map(|model| model.datname).collect())
+        .map_err(Into::into)
+    }
+
+    async fn get_previous_database_comments(
+        &self,
+        conn: &mut DatabaseConnection,
+    ) -> Result<Vec<(String, Option<String>)>, QueryError> {
+        #[derive(FromQueryResult)]
+        struct QueryModel {
+            datname: String,
+            description: Option<String>,
+        }
+
+        QueryModel::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            postgres::GET_DATABASE_COMMENTS_WITH_PREFIX,
+            [format!("{}_", self.db_name_prefix).into()],
+        ))
+        .all(conn)
+        .await
+        .map(|models| {
+            models
+                .into_iter()
+                .map(|model| (model.datname, model.description))
+                .collect()
+        })
+        .map_err(Into::into)
+    }
+
+    async fn create_entities(
+        &self,
+        db_id: Uuid,
+        stage: usize,
+        conn: DatabaseConnection,
+    ) -> Result<Option<DatabaseConnection>, QueryError> {
+        (self.create_entities[stage])(db_id, self.get_db_name(db_id), conn.clone()).await?;
+        Ok(Some(conn))
+    }
+
+    async fn create_entities_with_pool(&self, db_id: Uuid, pool: DatabaseConnection) {
+        if let Some(create_entities) = &self.create_entities_with_pool {
+            create_entities(db_id, self.get_db_name(db_id), pool).await;
+        }
+    }
+
+    async fn create_connection_pool(&self, db_id: Uuid) -> Result<DatabaseConnection, BuildError> {
+        let db_name = self.get_db_name(db_id);
+        let db_name = db_name.as_str();
+        let role_name = self.get_role_name(db_id);
+        let password = self.get_restricted_password(db_id);
+        let database_url =
+            self.privileged_config
+                .restricted_connection_url(&RestrictedConnectionParams {
+                    role: role_name.as_str(),
+                    password: Some(password.as_str()),
+                    ..RestrictedConnectionParams::for_database(db_name)
+                });
+        self.build_connection_pool(database_url).await
+    }
+
+    async fn warm_up_pool(&self, pool: &DatabaseConnection, count: u32) -> Result<(), PoolError> {
+        let pool = pool.get_postgres_connection_pool();
+        try_join_all((0..count).map(|_| pool.acquire()))
+            .await
+            .map_err(|err| DbErr::Conn(RuntimeErr::SqlxError(err)))?;
+        Ok(())
+    }
+
+    async fn create_privileged_connection_pool(
+        &self,
+        db_id: Uuid,
+    ) -> Result<DatabaseConnection, BuildError> {
+        let db_name = self.get_db_name(db_id);
+        let database_url = self
+            .owner_config
+            .as_ref()
+            .unwrap_or(&self.privileged_config)
+            .privileged_database_connection_url(db_name.as_str());
+        self.build_connection_pool(database_url).await
+    }
+
+    async fn get_table_names(
+        &self,
+        conn: &mut DatabaseConnection,
+    ) -> Result<Vec<(String, String)>, QueryError> {
+        #[derive(Clone, Debug, DeriveEntityModel)]
+        #[sea_orm(table_name = "pg_tables")]
+        pub struct Model {
+            schemaname: String,
+            #[sea_orm(primary_key)]
+            tablename: String,
+        }
+
+        #[derive(Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+
+        #[derive(FromQueryResult)]
+        struct QueryModel {
+            schemaname: String,
+            tablename: String,
+        }
+
+        Entity::find()
+            .select_only()
+            .column(Column::Schemaname)
+            .column(Column::Tablename)
+            .filter(Column::Schemaname.is_not_in(["pg_catalog", "information_schema"]))
+            .into_model::<QueryModel>()
+            .all(conn)
+            .await
+            .map(|mut models| {
+                models
+                    .drain(..)
+                    .map(|model| (model.schemaname, model.tablename))
+                    .collect()
+            })
+            .map_err(Into::into)
+    }
+
+    async fn get_partition_names(
+        &self,
+        conn: &mut DatabaseConnection,
+    ) -> Result<Vec<(String, String)>, QueryError> {
+        #[derive(FromQueryResult)]
+        struct QueryModel {
+            tablename: String,
+            schemaname: String,
+        }
+
+        QueryModel::find_by_statement(Statement::from_string(
+            DbBackend::Postgres,
+            postgres::GET_PARTITION_NAMES,
+        ))
+        .all(conn)
+        .await
+        .map(|models| {
+            models
+                .into_iter()
+                .map(|model| (model.schemaname, model.tablename))
+                .collect()
+        })
+        .map_err(Into::into)
+    }
+
+    async fn get_sequence_names(
+        &self,
+        conn: &mut DatabaseConnection,
+    ) -> Result<Vec<(String, String)>, QueryError> {
+        #[derive(Clone, Debug, DeriveEntityModel)]
+        #[sea_orm(table_name = "pg_sequences")]
+        pub struct Model {
+            schemaname: String,
+            #[sea_orm(primary_key)]
+            sequencename: String,
+        }
+
+        #[derive(Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+
+        #[derive(FromQueryResult)]
+        struct QueryModel {
+            schemaname: String,
+            sequencename: String,
+        }
+
+        Entity::find()
+            .select_only()
+            .column(Column::Schemaname)
+            .column(Column::Sequencename)
+            .filter(Column::Schemaname.is_not_in(["pg_catalog", "information_schema"]))
+            .into_model::<QueryModel>()
+            .all(conn)
+            .await
+            .map(|mut models| {
+                models
+                    .drain(..)
+                    .map(|model| (model.schemaname, model.sequencename))
+                    .collect()
+            })
+            .map_err(Into::into)
+    }
+
+    async fn get_role_attributes(
+        &self,
+        privileged_conn: &mut DatabaseConnection,
+    ) -> Result<RoleAttributes, QueryError> {
+        #[derive(FromQueryResult)]
+        struct QueryModel {
+            rolsuper: bool,
+            rolcreatedb: bool,
+            rolcreaterole: bool,
+        }
+
+        let QueryModel {
+            rolsuper,
+            rolcreatedb,
+            rolcreaterole,
+        } = QueryModel::find_by_statement(Statement::from_string(
+            DbBackend::Postgres,
+            postgres::GET_ROLE_ATTRIBUTES,
+        ))
+        .one(privileged_conn)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound("role of current user".to_owned()))?;
+
+        Ok(RoleAttributes {
+            superuser: rolsuper,
+            create_db: rolcreatedb,
+            create_role: rolcreaterole,
+        })
+    }
+
+    fn get_drop_previous_databases(&self) -> bool {
+        self.drop_previous_databases_flag
+    }
+
+    fn get_drop_previous_databases_concurrency(&self) -> Option<usize> {
+        self.drop_previous_databases_concurrency
+    }
+
+    fn get_create_entities_stages(&self) -> usize {
+        self.create_entities.len()
+    }
+
+    fn get_analyze_after_create(&self) -> bool {
+        self.analyze_after_create_flag
+    }
+
+    fn get_role_validity(&self) -> Option<Duration> {
+        self.role_validity
+    }
+
+    fn get_drop_grace_period(&self) -> Option<Duration> {
+        self.drop_grace_period
+    }
+
+    async fn before_drop(&self, db_id: Uuid, conn: DatabaseConnection) {
+        if let Some(before_drop) = &self.before_drop {
+            before_drop(db_id, self.get_db_name(db_id), conn).await;
+        }
+    }
+
+    async fn on_init(&self, conn: DatabaseConnection) {
+        if let Some(on_init) = &self.on_init {
+            on_init(conn).await;
+        }
+    }
+
+    fn has_before_drop(&self) -> bool {
+        self.before_drop.is_some()
+    }
+
+    fn has_on_init(&self) -> bool {
+        self.on_init.is_some()
+    }
+
+    fn has_create_entities_with_pool(&self) -> bool {
+        self.create_entities_with_pool.is_some()
+    }
+
+    fn get_database_template(&self) -> &DatabaseTemplate {
+        &self.database_template
+    }
+
+    fn get_database_owner(&self) -> Option<&str> {
+        self.owner_config
+            .as_ref()
+            .map(|config| config.username.as_str())
+    }
+
+    fn get_excluded_tables(&self) -> &[String] {
+        &self.excluded_tables
+    }
+
+    fn get_entity_template_id(&self) -> Option<Uuid> {
+        self.entity_template_id
+    }
+
+    fn get_verify_query(&self) -> Option<&str> {
+        self.verify_query.as_deref()
+    }
+
+    fn get_db_name_prefix(&self) -> &str {
+        self.db_name_prefix.as_str()
+    }
+
+    fn get_connection_retries(&self) -> Option<(usize, Duration)> {
+        self.connection_retries
+    }
+
+    fn get_restricted_privileges(&self) -> &RestrictedPrivileges {
+        &self.restricted_privileges
+    }
+
+    fn get_extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    fn get_maintenance_mode(&self) -> &MaintenanceMode {
+        &self.maintenance_mode
+    }
+
+    fn get_clean_batch_size(&self) -> usize {
+        self.clean_batch_size
+    }
+
+    fn get_truncate_mode(&self) -> &TruncateMode {
+        &self.truncate_mode
+    }
+
+    fn get_dry_run(&self) -> bool {
+        self.dry_run_flag
+    }
+
+    fn get_restricted_password(&self, db_id: Uuid) -> String {
+        self.restricted_password
+            .as_ref()
+            .map_or_else(|| self.get_db_name(db_id), |password| password(db_id))
+    }
+
+    fn get_reassign_owned_roles(&self) -> &[String] {
+        &self.reassign_owned_roles
+    }
+
+    fn get_clean_schemas(&self) -> &[String] {
+        &self.clean_schemas
+    }
+
+    fn get_fk_handling(&self) -> &FkHandling {
+        &self.fk_handling
+    }
+
+    fn get_observer(&self) -> Option<&dyn Observer> {
+        self.observer.as_deref()
+    }
+
+    fn get_warn_previous_databases(&self) -> bool {
+        self.warn_previous_databases_flag
+    }
+
+    fn get_statement_timeout(&self) -> Option<Duration> {
+        self.statement_timeout
+    }
+
+    fn get_unrestricted_database_owner(&self) -> Option<&str> {
+        self.unrestricted_owner_role.as_deref()
+    }
+
+    fn get_terminate_connections_before_drop(&self) -> bool {
+        self.terminate_connections_flag
+    }
+
+    fn get_connection_statements(&self) -> &[String] {
+        &self.connection_statements
+    }
+
+    fn get_db_name_generator(&self) -> Option<&DbNameGenerator> {
+        self.db_name_generator.as_ref()
+    }
+
+    fn get_max_create_retries(&self) -> usize {
+        self.max_create_retries
+    }
+
+    fn get_truncate_partition_parents_only(&self) -> bool {
+        self.truncate_partition_parents_only_flag
+    }
+
+    fn get_previous_databases_ttl(&self) -> Option<Duration> {
+        self.previous_databases_ttl
+    }
+
+    fn get_warm_up_pools(&self) -> bool {
+        self.warm_up_pools_flag
+    }
+
+    fn get_restricted_min_idle(&self) -> Option<u32> {
+        self.restricted_min_idle
+    }
+
+    fn get_clean_table_statements(&self, table_name: &str) -> Option<Vec<String>> {
+        self.clean_table
+            .as_ref()
+            .and_then(|clean_table| clean_table(table_name))
+    }
+
+    fn get_create_jitter(&self) -> Option<Duration> {
+        self.create_jitter
+    }
+
+    fn get_role_creation_lock(&self) -> Option<&AsyncMutex<()>> {
+        self.role_creation_lock.as_ref()
+    }
+
+    fn get_create_entities_in_transaction(&self) -> bool {
+        false
+    }
+
+    fn get_drop_lock_timeout(&self) -> Option<Duration> {
+        // Statements over the default pool may not share a connection
+        None
+    }
+
+    fn get_database_locale(&self) -> &DatabaseLocale {
+        &self.database_locale
+    }
+
+    fn get_role_options(&self) -> &RoleOptions {
+        &self.role_options
+    }
+
+    fn get_shared_role(&self) -> Option<&str> {
+        self.shared_role.as_deref()
+    }
+
+    fn get_default_pool_breaker(&self) -> Option<&CircuitBreaker> {
+        self.default_pool_breaker.as_ref()
+    }
+}
+
+type BError = BackendError<BuildError, PoolError, ConnectionError, QueryError>;
+
+#[async_trait]
+impl Backend for SeaORMPostgresBackend {
+    type Pool = DatabaseConnection;
+    type Connection = DatabaseConnection;
+
+    type BuildError = BuildError;
+    type PoolError = PoolError;
+    type ConnectionError = ConnectionError;
+    type QueryError = QueryError;
+
+    async fn init(&self) -> Result<(), BError> {
+        PostgresBackendWrapper::new(self).init().await
+    }
+
+    async fn create(
+        &self,
+        db_id: uuid::Uuid,
+        restrict_privileges: bool,
+    ) -> Result<DatabaseConnection, BError> {
+        PostgresBackendWrapper::new(self)
+            .create(db_id, restrict_privileges)
+            .await
+    }
+
+    async fn clean(&self, db_id: uuid::Uuid) -> Result<(), BError> {
+        PostgresBackendWrapper::new(self).clean(db_id).await
+    }
+
+    async fn clean_tables(&self, db_id: uuid::Uuid, table_names: &[&str]) -> Result<(), BError> {
+        PostgresBackendWrapper::new(self)
+            .clean_tables(db_id, table_names)
+            .await
+    }
+
+    async fn reset_sequences(&self, db_id: uuid::Uuid) -> Result<(), BError> {
+        PostgresBackendWrapper::new(self)
+            .reset_sequences(db_id)
+            .await
+    }
+
+    async fn rebuild_pool(&self, db_id: uuid::Uuid) -> Result<DatabaseConnection, BError> {
+        PostgresBackendWrapper::new(self).rebuild_pool(db_id).await
+    }
+
+    async fn create_connection(
+        &self,
+        db_id: uuid::Uuid,
+        restrict_privileges: bool,
+    ) -> Result<Self::Connection, BError> {
+        PostgresBackendWrapper::new(self)
+            .create_connection(db_id, restrict_privileges)
+            .await
+    }
+
+    async fn database_exists(&self, db_id: uuid::Uuid) -> Result<bool, BError> {
+        PostgresBackendWrapper::new(self)
+            .database_exists(db_id)
+            .await
+    }
+
+    async fn snapshot(&self, db_id: uuid::Uuid) -> Result<(), BError> {
+        PostgresBackendWrapper::new(self).snapshot(db_id).await
+    }
+
+    async fn restore(&self, db_id: uuid::Uuid) -> Result<(), BError> {
+        PostgresBackendWrapper::new(self).restore(db_id).await
+    }
+
+    async fn drop(&self, db_id: uuid::Uuid, is_restricted: bool) -> Result<(), BError> {
+        PostgresBackendWrapper::new(self)
+            .drop(db_id, is_restricted)
+            .await
+    }
+
+    async fn verify(&self) -> Result<(), BError> {
+        PostgresBackendWrapper::new(self).verify().await
+    }
+
+    fn get_max_create_retries(&self) -> usize {
+        self.max_create_retries
+    }
+
+    fn get_initial_size(&self) -> usize {
+        self.initial_size
+    }
+
+    fn get_database_name(&self, db_id: Uuid) -> String {
+        self.get_db_name(db_id)
+    }
+
+    fn get_keep_databases_on_panic(&self) -> bool {
+        self.keep_databases_on_panic_flag
+    }
+
+    fn get_drop_timeout(&self) -> Option<Duration> {
+        self.drop_timeout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::needless_return)]
+
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use dotenvy::dotenv;
+    use futures::future::{join, join_all};
+    use parking_lot::Mutex;
+    use sea_orm::{
+        ActiveModelBehavior, ActiveModelTrait, ConnectionTrait, DeriveEntityModel,
+        DerivePrimaryKey, DeriveRelation, EntityTrait, EnumIter, FromQueryResult, PaginatorTrait,
+        PrimaryKeyTrait, QuerySelect, Set,
+    };
+    use tokio_shared_rt::test;
+    use uuid::Uuid;
+
+    use crate::{
+        r#async::{
+            backend::postgres::r#trait::tests::{
+                test_backend_discards_database_when_creating_entities_fails,
+                test_backend_drops_database, test_backend_tolerates_concurrent_creates,
+                test_pool_drops_created_unrestricted_database,
+            },
+            db_pool::DatabasePoolBuilder,
+        },
+        common::{
+            config::PrivilegedPostgresConfig,
+            statement::postgres::tests::{
+                CREATE_AUTHOR, CREATE_ENTITIES_STATEMENTS, CREATE_INVOICE_STATEMENTS,
+                DDL_STATEMENTS, DML_STATEMENTS, INSERT_BOOK,
+            },
+        },
+    };
+
+    use super::{
+        super::{
+            maintenance::MaintenanceMode,
+            privileges::RestrictedPrivileges,
+            template::{DatabaseLocale, DatabaseTemplate},
+            r#trait::tests::{
+                CountingObserver, PgDropLock, collide_db_names, count_db_names, create_owner_role,
+                fail_first_clean, test_backend_analyzes_created_database,
+                test_backend_checks_database_existence,
+                test_backend_cleans_database_disabling_triggers,
+                test_backend_cleans_database_except_excluded_tables,
+                test_backend_cleans_database_in_schemas,
+                test_backend_cleans_database_restarting_identity,
+                test_backend_cleans_database_with_many_tables,
+                test_backend_cleans_database_with_tables,
+                test_backend_cleans_database_without_tables, test_backend_cleans_partitioned_table,
+                test_backend_cleans_subset_of_tables,
+                test_backend_cleans_table_with_custom_statements,
+                test_backend_cleans_tables_with_quoted_names, test_backend_clones_entity_template,
+                test_backend_creates_database_owned_by_owner,
+                test_backend_creates_database_with_collation,
+                test_backend_creates_database_with_configured_owner,
+                test_backend_creates_database_with_restricted_privileges,
+                test_backend_creates_database_with_unrestricted_privileges,
+                test_backend_creates_databases_concurrently,
+                test_backend_creates_databases_with_generated_names,
+                test_backend_creates_entities_in_stages, test_backend_creates_entities_with_pool,
+                test_backend_creates_extensions, test_backend_creates_read_only_database,
+                test_backend_drops_database_with_lingering_connection,
+                test_backend_drops_database_within_grace_period,
+                test_backend_drops_only_expired_previous_databases,
+                test_backend_drops_previous_databases,
+                test_backend_drops_previous_databases_with_concurrency,
+                test_backend_drops_previous_databases_with_own_prefix,
+                test_backend_grants_configured_restricted_privileges,
+                test_backend_grants_restricted_privileges_in_schemas,
+                test_backend_keeps_connection_after_failed_clean,
+                test_backend_passes_context_to_entity_creation,
+                test_backend_rebuilds_connection_pool, test_backend_rejects_invalid_generated_name,
+                test_backend_reports_existing_database, test_backend_reports_failed_statement,
+                test_backend_reports_lifecycle_to_observer, test_backend_resets_sequences,
+                test_backend_retries_clean_after_failed_attempt,
+                test_backend_runs_hook_before_drop, test_backend_runs_verify_query,
+                test_backend_sets_statement_timeout, test_backend_shares_role,
+                test_backend_skips_statements_in_dry_run, test_backend_uses_restricted_password,
+                test_backend_verifies_privileges, test_backend_warns_about_previous_databases,
+                test_pool_caps_checked_out_databases, test_pool_cleans_database_on_demand,
+                test_pool_closes_and_drops_databases, test_pool_creates_initial_databases,
+                test_pool_drops_created_restricted_databases, test_pool_drops_previous_databases,
+                test_pool_exposes_database_name, test_pool_exposes_privilege_restriction,
+                test_pool_gives_up_dropping_database_after_timeout,
+                test_pool_keeps_database_on_panic, test_pool_pulls_single_connection,
+                test_pool_rebuilds_connection_pools, test_pool_rejects_pull_after_shutdown,
+                test_pool_reports_provision_timings, test_pool_reports_stats,
+                test_pool_snapshots_and_restores_database,
+            },
+            truncate::{FkHandling, TruncateMode},
+        },
+        SeaORMPostgresBackend,
+    };
+
+    #[derive(Clone, Debug, DeriveEntityModel)]
+    #[sea_orm(table_name = "book")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        id: i32,
+        title: String,
+    }
+
+    #[derive(Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    async fn create_backend(with_table: bool) -> SeaORMPostgresBackend {
+        dotenv().ok();
+
+        let config = PrivilegedPostgresConfig::from_env().unwrap();
+
+        SeaORMPostgresBackend::new(config, |_| {}, |_| {}, {
+            move |conn| {
+                if with_table {
+                    Box::pin(async move {
+                        conn.execute_unprepared(CREATE_ENTITIES_STATEMENTS.join(";").as_str())
+                            .await
+                            .unwrap();
+                    })
+                } else {
+                    Box::pin(async {})
+                }
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_previous_databases() {
+        Box::pin(test_backend_drops_previous_databases(
+            create_backend(false).await,
+            create_backend(false).await.drop_previous_databases(true),
+            create_backend(false).await.drop_previous_databases(false),
+        ))
+        .await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_database_with_restricted_privileges() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_creates_database_with_restricted_privileges(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_database_with_unrestricted_privileges() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_creates_database_with_unrestricted_privileges(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_with_tables() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_cleans_database_with_tables(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_with_tables_running_analyze() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .maintenance_mode(MaintenanceMode::Analyze);
+        test_backend_cleans_database_with_tables(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_with_tables_running_vacuum() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .maintenance_mode(MaintenanceMode::Vacuum);
+        test_backend_cleans_database_with_tables(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_without_tables() {
+        let backend = create_backend(false).await.drop_previous_databases(false);
+        test_backend_cleans_database_without_tables(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_restricted_database() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_drops_database(backend, true).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_unrestricted_database() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_drops_database(backend, false).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_analyzes_created_restricted_database() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .analyze_after_create(true);
+        test_backend_analyzes_created_database(backend, true).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_analyzes_created_unrestricted_database() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .analyze_after_create(true);
+        test_backend_analyzes_created_database(backend, false).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_reports_existing_database() {
+        let backend = create_backend(false).await.drop_previous_databases(false);
+        test_backend_reports_existing_database(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_database_with_role_validity() {
+        let backend = create_backend(false)
+            .await
+            .drop_previous_databases(false)
+            .role_validity(Duration::from_hours(1));
+        test_backend_creates_database_with_unrestricted_privileges(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_runs_hook_before_dropping_restricted_database() {
+        let dropped_db_names = Arc::new(Mutex::new(Vec::new()));
+        let backend = {
+            let dropped_db_names = dropped_db_names.clone();
+            create_backend(true)
+                .await
+                .drop_previous_databases(false)
+                .before_drop(move |_, db_name, _| {
+                    dropped_db_names.lock().push(db_name);
+                    Box::pin(async {})
+                })
+        };
+        test_backend_runs_hook_before_drop(backend, true, dropped_db_names).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_runs_hook_before_dropping_unrestricted_database() {
+        let dropped_db_names = Arc::new(Mutex::new(Vec::new()));
+        let backend = {
+            let dropped_db_names = dropped_db_names.clone();
+            create_backend(true)
+                .await
+                .drop_previous_databases(false)
+                .before_drop(move |_, db_name, _| {
+                    dropped_db_names.lock().push(db_name);
+                    Box::pin(async {})
+                })
+        };
+        test_backend_runs_hook_before_drop(backend, false, dropped_db_names).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_previous_databases_with_concurrency() {
+        Box::pin(test_backend_drops_previous_databases_with_concurrency(
+            create_backend(false)
+                .await
+                .drop_previous_databases_concurrency(1),
+            create_backend(false)
+                .await
+                .drop_previous_databases_concurrency(3),
+        ))
+        .await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_entities_in_stages_for_restricted_database() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .then_create_entities(|conn| {
+                Box::pin(async move {
+                    conn.execute_unprepared(INSERT_BOOK).await.unwrap();
+                })
+            });
+        test_backend_creates_entities_in_stages(backend, true).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_entities_in_stages_for_unrestricted_database() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .then_create_entities(|conn| {
+                Box::pin(async move {
+                    conn.execute_unprepared(INSERT_BOOK).await.unwrap();
+                })
+            });
+        test_backend_creates_entities_in_stages(backend, false).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_discards_database_when_creating_entities_fails() {
+        let backend = create_backend(false)
+            .await
+            .drop_previous_databases(false)
+            .then_try_create_entities(|conn| {
+                Box::pin(async move {
+                    conn.execute_unprepared("INSERT INTO missing DEFAULT VALUES")
+                        .await?;
+                    Ok(())
+                })
+            });
+        test_backend_discards_database_when_creating_entities_fails(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_entities_with_pool_for_restricted_database() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .create_entities_with_pool(|_, _, pool| {
+                Box::pin(async move {
+                    let (book, author) = join(
+                        pool.execute_unprepared(INSERT_BOOK),
+                        pool.execute_unprepared(CREATE_AUTHOR),
+                    )
+                    .await;
+                    book.unwrap();
+                    author.unwrap();
+                })
+            });
+        test_backend_creates_entities_with_pool(backend, true).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_entities_with_pool_for_unrestricted_database() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .create_entities_with_pool(|_, _, pool| {
+                Box::pin(async move {
+                    let (book, author) = join(
+                        pool.execute_unprepared(INSERT_BOOK),
+                        pool.execute_unprepared(CREATE_AUTHOR),
+                    )
+                    .await;
+                    book.unwrap();
+                    author.unwrap();
+                })
+            });
+        test_backend_creates_entities_with_pool(backend, false).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_passes_context_to_entity_creation() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .then_create_entities_with_context(|_, db_name, conn| {
+                Box::pin(async move {
+                    conn.execute_unprepared(&format!(
+                        "INSERT INTO book (title) VALUES ('{db_name}')"
+                    ))
+                    .await
+                    .unwrap();
+                })
+            });
+        test_backend_passes_context_to_entity_creation(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_database_within_grace_period() {
+        let grace_period = Duration::from_secs(5);
+        let backend = create_backend(false)
+            .await
+            .drop_previous_databases(false)
+            .drop_grace_period(grace_period);
+        test_backend_drops_database_within_grace_period(backend, grace_period).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_rebuilds_connection_pool() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_rebuilds_connection_pool(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_database_from_template0() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .database_template(DatabaseTemplate::Template0 {
+                encoding: "UTF8".to_owned(),
+                locale: "C".to_owned(),
+            });
+        test_backend_creates_database_with_unrestricted_privileges(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_subset_of_tables() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_cleans_subset_of_tables(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_restarting_identity() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_cleans_database_restarting_identity(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_database_owned_by_owner() {
+        let owner = create_owner_role().await;
+        let config = PrivilegedPostgresConfig::from_env()
+            .unwrap()
+            .username(owner.clone())
+            .password(Some(owner.clone()));
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .owner_config(config);
+        test_backend_creates_database_owned_by_owner(backend, owner.as_str()).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_except_excluded_tables() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .exclude_tables_from_cleaning(vec!["dummy".to_owned()]);
+        test_backend_cleans_database_except_excluded_tables(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_clones_entity_template() {
+        let entity_creations = Arc::new(AtomicUsize::new(0));
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .create_entities_once(true)
+            .then_create_entities({
+                let entity_creations = entity_creations.clone();
+                move |conn| {
+                    entity_creations.fetch_add(1, Ordering::Relaxed);
+                    Box::pin(async move {
+                        drop(conn);
+                    })
+                }
+            });
+        test_backend_clones_entity_template(backend, entity_creations).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_runs_verify_query() {
+        test_backend_runs_verify_query(
+            create_backend(true)
+                .await
+                .drop_previous_databases(false)
+                .verify_query("SELECT 1 FROM book LIMIT 0".to_owned()),
+            create_backend(true)
+                .await
+                .drop_previous_databases(false)
+                .verify_query("SELECT 1 FROM author LIMIT 0".to_owned()),
+        )
+        .await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_previous_databases_with_own_prefix() {
+        test_backend_drops_previous_databases_with_own_prefix(
+            create_backend(false).await,
+            create_backend(false)
+                .await
+                .db_name_prefix("db_pool_other".to_owned()),
+            "db_pool_other",
+        )
+        .await;
     }
 
-    async fn create_entities(&self, conn: DatabaseConnection) -> Option<DatabaseConnection> {
-        (self.create_entities)(conn.clone()).await;
-        Some(conn)
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_resets_sequences() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_resets_sequences(backend).await;
     }
 
-    async fn create_connection_pool(&self, db_id: Uuid) -> Result<DatabaseConnection, BuildError> {
-        let db_name = get_db_name(db_id);
-        let db_name = db_name.as_str();
-        let database_url = self.privileged_config.restricted_database_connection_url(
-            db_name,
-            Some(db_name),
-            db_name,
-        );
-        let mut opts = ConnectOptions::new(database_url);
-        (self.create_restricted_pool)(&mut opts);
-        Database::connect(opts).await.map_err(Into::into)
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_grants_configured_restricted_privileges() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .restricted_privileges(
+                RestrictedPrivileges::none()
+                    .select(true)
+                    .insert(true)
+                    .sequence_usage(true),
+            );
+        test_backend_grants_configured_restricted_privileges(backend).await;
     }
 
-    async fn get_table_names(
-        &self,
-        conn: &mut DatabaseConnection,
-    ) -> Result<Vec<String>, QueryError> {
-        #[derive(Clone, Debug, DeriveEntityModel)]
-        #[sea_orm(table_name = "pg_tables")]
-        pub struct Model {
-            schemaname: String,
-            #[sea_orm(primary_key)]
-            tablename: String,
-        }
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_extensions() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .extensions(vec!["pgcrypto".to_owned()]);
+        test_backend_creates_extensions(backend).await;
+    }
 
-        #[derive(Debug, EnumIter, DeriveRelation)]
-        pub enum Relation {}
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_with_many_tables_in_batches() {
+        let backend = create_backend(false)
+            .await
+            .drop_previous_databases(false)
+            .clean_batch_size(10);
+        test_backend_cleans_database_with_many_tables(backend).await;
+    }
 
-        impl ActiveModelBehavior for ActiveModel {}
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_with_many_tables_in_multi_table_batches() {
+        let backend = create_backend(false)
+            .await
+            .drop_previous_databases(false)
+            .clean_batch_size(10)
+            .truncate_mode(TruncateMode::MultiTable);
+        test_backend_cleans_database_with_many_tables(backend).await;
+    }
 
-        #[derive(FromQueryResult)]
-        struct QueryModel {
-            tablename: String,
-        }
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_verifies_privileges() {
+        let backend = create_backend(false).await.drop_previous_databases(false);
+        test_backend_verifies_privileges(backend).await;
+    }
 
-        Entity::find()
-            .select_only()
-            .column(Column::Tablename)
-            .filter(Column::Schemaname.is_not_in(["pg_catalog", "information_schema"]))
-            .into_model::<QueryModel>()
-            .all(conn)
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_skips_statements_in_dry_run() {
+        let backend = create_backend(true)
             .await
-            .map(|mut models| models.drain(..).map(|model| model.tablename).collect())
-            .map_err(Into::into)
+            .drop_previous_databases(false)
+            .dry_run(true);
+        test_backend_skips_statements_in_dry_run(backend).await;
     }
 
-    fn get_drop_previous_databases(&self) -> bool {
-        self.drop_previous_databases_flag
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_uses_restricted_password() {
+        let password = Uuid::new_v4().simple().to_string();
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .restricted_password({
+                let password = password.clone();
+                move |_| password.clone()
+            });
+        test_backend_uses_restricted_password(backend, password.as_str()).await;
     }
-}
-
-type BError = BackendError<BuildError, PoolError, ConnectionError, QueryError>;
 
-#[async_trait]
-impl Backend for SeaORMPostgresBackend {
-    type Pool = DatabaseConnection;
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_reports_failed_statement() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_backend_reports_failed_statement(backend).await;
+    }
 
-    type BuildError = BuildError;
-    type PoolError = PoolError;
-    type ConnectionError = ConnectionError;
-    type QueryError = QueryError;
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_in_schemas() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .clean_schemas(vec!["public".to_owned(), "billing".to_owned()]);
+        test_backend_cleans_database_in_schemas(backend).await;
+    }
 
-    async fn init(&self) -> Result<(), BError> {
-        PostgresBackendWrapper::new(self).init().await
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_tables_with_quoted_names() {
+        let backend = create_backend(false).await.drop_previous_databases(false);
+        test_backend_cleans_tables_with_quoted_names(backend).await;
     }
 
-    async fn create(
-        &self,
-        db_id: uuid::Uuid,
-        restrict_privileges: bool,
-    ) -> Result<DatabaseConnection, BError> {
-        PostgresBackendWrapper::new(self)
-            .create(db_id, restrict_privileges)
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_database_disabling_triggers() {
+        let backend = create_backend(false)
             .await
+            .drop_previous_databases(false)
+            .exclude_tables_from_cleaning(vec!["review".to_owned()])
+            .fk_handling(FkHandling::DisableTriggers);
+        test_backend_cleans_database_disabling_triggers(backend).await;
     }
 
-    async fn clean(&self, db_id: uuid::Uuid) -> Result<(), BError> {
-        PostgresBackendWrapper::new(self).clean(db_id).await
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_read_only_database() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .restricted_privileges(RestrictedPrivileges::read_only());
+        test_backend_creates_read_only_database(backend).await;
     }
 
-    async fn drop(&self, db_id: uuid::Uuid, is_restricted: bool) -> Result<(), BError> {
-        PostgresBackendWrapper::new(self)
-            .drop(db_id, is_restricted)
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_reports_lifecycle_to_observer() {
+        let observer = CountingObserver::default();
+        let backend = create_backend(true)
             .await
+            .drop_previous_databases(false)
+            .observer(observer.clone());
+        test_backend_reports_lifecycle_to_observer(backend, observer).await;
     }
-}
-
-#[cfg(test)]
-mod tests {
-    #![allow(clippy::unwrap_used, clippy::needless_return)]
 
-    use dotenvy::dotenv;
-    use futures::future::join_all;
-    use sea_orm::{
-        ActiveModelBehavior, ActiveModelTrait, ConnectionTrait, DeriveEntityModel,
-        DerivePrimaryKey, DeriveRelation, EntityTrait, EnumIter, FromQueryResult, PaginatorTrait,
-        PrimaryKeyTrait, QuerySelect, Set,
-    };
-    use tokio_shared_rt::test;
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_tolerates_concurrent_creates() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .db_name_generator(collide_db_names());
+        test_backend_tolerates_concurrent_creates(backend).await;
+    }
 
-    use crate::{
-        r#async::{
-            backend::postgres::r#trait::tests::{
-                test_backend_drops_database, test_pool_drops_created_unrestricted_database,
-            },
-            db_pool::DatabasePoolBuilder,
-        },
-        common::{
-            config::PrivilegedPostgresConfig,
-            statement::postgres::tests::{
-                CREATE_ENTITIES_STATEMENTS, DDL_STATEMENTS, DML_STATEMENTS,
-            },
-        },
-    };
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_warns_about_previous_databases() {
+        let backend = create_backend(false).await.warn_previous_databases(true);
+        test_backend_warns_about_previous_databases(backend).await;
+    }
 
-    use super::{
-        super::r#trait::tests::{
-            PgDropLock, test_backend_cleans_database_with_tables,
-            test_backend_cleans_database_without_tables,
-            test_backend_creates_database_with_restricted_privileges,
-            test_backend_creates_database_with_unrestricted_privileges,
-            test_backend_drops_previous_databases, test_pool_drops_created_restricted_databases,
-            test_pool_drops_previous_databases,
-        },
-        SeaORMPostgresBackend,
-    };
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_sets_statement_timeout() {
+        let backend = create_backend(true)
+            .await
+            .statement_timeout(Duration::from_millis(100))
+            .drop_previous_databases(false);
+        test_backend_sets_statement_timeout(backend).await;
+    }
 
-    #[derive(Clone, Debug, DeriveEntityModel)]
-    #[sea_orm(table_name = "book")]
-    pub struct Model {
-        #[sea_orm(primary_key)]
-        id: i32,
-        title: String,
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_database_with_configured_owner() {
+        let owner = create_owner_role().await;
+        let backend = create_backend(true)
+            .await
+            .unrestricted_database_owner(owner.clone())
+            .drop_previous_databases(false);
+        test_backend_creates_database_with_configured_owner(backend, owner.as_str()).await;
     }
 
-    #[derive(Debug, EnumIter, DeriveRelation)]
-    pub enum Relation {}
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_database_with_lingering_connection() {
+        let backend = create_backend(true)
+            .await
+            .terminate_connections_before_drop(true)
+            .drop_previous_databases(false);
+        test_backend_drops_database_with_lingering_connection(backend).await;
+    }
 
-    impl ActiveModelBehavior for ActiveModel {}
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_creates_databases_with_generated_names() {
+        let backend = create_backend(true)
+            .await
+            .db_name_generator(count_db_names());
+        test_backend_creates_databases_with_generated_names(backend).await;
+    }
 
-    async fn create_backend(with_table: bool) -> SeaORMPostgresBackend {
-        dotenv().ok();
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_partitioned_table() {
+        let backend = create_backend(false)
+            .await
+            .drop_previous_databases(false)
+            .truncate_partition_parents_only(true);
+        test_backend_cleans_partitioned_table(backend).await;
+    }
 
-        let config = PrivilegedPostgresConfig::from_env().unwrap();
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_drops_only_expired_previous_databases() {
+        let backend = create_backend(true)
+            .await
+            .previous_databases_ttl(Duration::from_hours(1));
+        test_backend_drops_only_expired_previous_databases(backend).await;
+    }
 
-        SeaORMPostgresBackend::new(config, |_| {}, |_| {}, {
-            move |conn| {
-                if with_table {
-                    Box::pin(async move {
-                        conn.execute_unprepared(CREATE_ENTITIES_STATEMENTS.join(";").as_str())
-                            .await
-                            .unwrap();
-                    })
-                } else {
-                    Box::pin(async {})
-                }
-            }
-        })
-        .await
-        .unwrap()
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_cleans_table_with_custom_statements() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .clean_table_with(|table_name| {
+                // preserve seed book
+                (table_name == "book")
+                    .then(|| vec!["DELETE FROM book WHERE title <> 'Title 1'".to_owned()])
+            });
+        test_backend_cleans_table_with_custom_statements(backend).await;
     }
 
     #[test(flavor = "multi_thread", shared)]
-    async fn backend_drops_previous_databases() {
-        test_backend_drops_previous_databases(
-            create_backend(false).await,
-            create_backend(false).await.drop_previous_databases(true),
-            create_backend(false).await.drop_previous_databases(false),
-        )
-        .await;
+    async fn backend_creates_databases_concurrently() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .create_jitter(Duration::from_millis(100))
+            .serialize_role_creation(true);
+        test_backend_creates_databases_concurrently(backend).await;
     }
 
     #[test(flavor = "multi_thread", shared)]
-    async fn backend_creates_database_with_restricted_privileges() {
+    async fn backend_checks_database_existence() {
         let backend = create_backend(true).await.drop_previous_databases(false);
-        test_backend_creates_database_with_restricted_privileges(backend).await;
+        test_backend_checks_database_existence(backend).await;
     }
 
     #[test(flavor = "multi_thread", shared)]
-    async fn backend_creates_database_with_unrestricted_privileges() {
-        let backend = create_backend(true).await.drop_previous_databases(false);
-        test_backend_creates_database_with_unrestricted_privileges(backend).await;
+    async fn backend_creates_database_with_collation() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .database_template(DatabaseTemplate::Named("template0".to_owned()))
+            .database_locale(DatabaseLocale {
+                encoding: Some("UTF8".to_owned()),
+                lc_collate: Some("C".to_owned()),
+                ..DatabaseLocale::default()
+            });
+        test_backend_creates_database_with_collation(backend, "C").await;
     }
 
     #[test(flavor = "multi_thread", shared)]
-    async fn backend_cleans_database_with_tables() {
-        let backend = create_backend(true).await.drop_previous_databases(false);
-        test_backend_cleans_database_with_tables(backend).await;
+    async fn backend_shares_role() {
+        let role = create_owner_role().await;
+        let password = role.clone();
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .shared_role(role.clone())
+            .restricted_password(move |_| password.clone());
+        test_backend_shares_role(backend, role.as_str()).await;
     }
 
     #[test(flavor = "multi_thread", shared)]
-    async fn backend_cleans_database_without_tables() {
-        let backend = create_backend(false).await.drop_previous_databases(false);
-        test_backend_cleans_database_without_tables(backend).await;
+    async fn backend_keeps_connection_after_failed_clean() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .statement_timeout(Duration::from_millis(500));
+        test_backend_keeps_connection_after_failed_clean(backend).await;
     }
 
     #[test(flavor = "multi_thread", shared)]
-    async fn backend_drops_restricted_database() {
-        let backend = create_backend(true).await.drop_previous_databases(false);
-        test_backend_drops_database(backend, true).await;
+    async fn backend_rejects_invalid_generated_name() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .db_name_generator(|| "other_test_1".to_owned());
+        test_backend_rejects_invalid_generated_name(backend).await;
     }
 
     #[test(flavor = "multi_thread", shared)]
-    async fn backend_drops_unrestricted_database() {
-        let backend = create_backend(true).await.drop_previous_databases(false);
-        test_backend_drops_database(backend, false).await;
+    async fn backend_grants_restricted_privileges_in_schemas() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .clean_schemas(vec!["public".to_owned(), "billing".to_owned()])
+            .then_create_entities(|conn| {
+                Box::pin(async move {
+                    for stmt in CREATE_INVOICE_STATEMENTS {
+                        conn.execute_unprepared(stmt).await.unwrap();
+                    }
+                })
+            });
+        test_backend_grants_restricted_privileges_in_schemas(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn backend_retries_clean_after_failed_attempt() {
+        let backend = create_backend(true)
+            .await
+            .drop_previous_databases(false)
+            .clean_table_with(fail_first_clean());
+        test_backend_retries_clean_after_failed_attempt(backend).await;
     }
 
     #[test(flavor = "multi_thread", shared)]
     async fn pool_drops_previous_databases() {
-        test_pool_drops_previous_databases(
+        Box::pin(test_pool_drops_previous_databases(
             create_backend(false).await,
             create_backend(false).await.drop_previous_databases(true),
             create_backend(false).await.drop_previous_databases(false),
-        )
+        ))
         .await;
     }
 
@@ -590,4 +2397,95 @@ mod tests {
         let backend = create_backend(false).await;
         test_pool_drops_created_unrestricted_database(backend).await;
     }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_rebuilds_connection_pools() {
+        let backend = create_backend(true).await;
+        test_pool_rebuilds_connection_pools(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_rejects_pull_after_shutdown() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_pool_rejects_pull_after_shutdown(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_reports_stats() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_pool_reports_stats(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_gives_up_dropping_database_after_timeout() {
+        let drop_timeout = Duration::from_secs(1);
+        let backend = create_backend(false)
+            .await
+            .drop_grace_period(Duration::from_secs(30))
+            .drop_timeout(drop_timeout);
+        test_pool_gives_up_dropping_database_after_timeout(backend, drop_timeout).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_creates_initial_databases() {
+        let backend = create_backend(true).await.initial_size(5);
+        test_pool_creates_initial_databases(backend, 5).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_keeps_database_on_panic() {
+        let backend = create_backend(false).await.keep_databases_on_panic(true);
+        test_pool_keeps_database_on_panic(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_exposes_database_name() {
+        let backend = create_backend(false).await.drop_previous_databases(false);
+        test_pool_exposes_database_name(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_exposes_privilege_restriction() {
+        let backend = create_backend(false).await.drop_previous_databases(false);
+        test_pool_exposes_privilege_restriction(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_pulls_single_connection() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_pool_pulls_single_connection(backend, async |conn| {
+            conn.execute_unprepared(INSERT_BOOK).await.unwrap();
+        })
+        .await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_cleans_database_on_demand() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_pool_cleans_database_on_demand(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_snapshots_and_restores_database() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_pool_snapshots_and_restores_database(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_reports_provision_timings() {
+        let backend = create_backend(true).await.initial_size(2);
+        test_pool_reports_provision_timings(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_caps_checked_out_databases() {
+        let backend = create_backend(true).await.drop_previous_databases(false);
+        test_pool_caps_checked_out_databases(backend).await;
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn pool_closes_and_drops_databases() {
+        let backend = create_backend(true).await;
+        test_pool_closes_and_drops_databases(backend).await;
+    }
 }
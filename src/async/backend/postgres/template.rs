@@ -0,0 +1,123 @@
+use crate::common::statement::postgres;
+
+/// Template database that created databases are copied from
+pub enum DatabaseTemplate {
+    /// ``template1``, which may carry locally installed objects
+    Default,
+    /// Pristine ``template0``, which requires an explicit encoding and locale
+    Template0 {
+        /// Encoding of created databases, such as ``UTF8``
+        encoding: String,
+        /// Locale of created databases, such as ``C``
+        locale: String,
+    },
+    /// Named template database
+    Named(String),
+}
+
+impl DatabaseTemplate {
+    pub(super) fn create_database(&self, db_name: &str, locale: &DatabaseLocale) -> String {
+        match self {
+            Self::Default => postgres::create_database(db_name) + locale.clauses().as_str(),
+            Self::Template0 {
+                encoding,
+                locale: template_locale,
+            } => {
+                // Merge settings, which Postgres rejects when given twice, preferring the database locale
+                let encoding = locale.encoding.as_deref().unwrap_or(encoding);
+                let (lc_collate, lc_ctype) =
+                    (locale.lc_collate.as_deref(), locale.lc_ctype.as_deref());
+                if lc_collate.is_none() && lc_ctype.is_none() {
+                    postgres::create_database_from_template(
+                        db_name,
+                        "template0",
+                        Some(encoding),
+                        Some(template_locale.as_str()),
+                    )
+                } else {
+                    postgres::create_database_from_template(db_name, "template0", None, None)
+                        + postgres::database_locale_clauses(
+                            Some(encoding),
+                            Some(lc_collate.unwrap_or(template_locale)),
+                            Some(lc_ctype.unwrap_or(template_locale)),
+                        )
+                        .as_str()
+                }
+            }
+            Self::Named(template) => {
+                postgres::create_database_from_template(db_name, template.as_str(), None, None)
+                    + locale.clauses().as_str()
+            }
+        }
+    }
+}
+
+/// Encoding and locale of created databases, each inherited from the template unless set
+///
+/// Settings differing from ``template1`` require [`DatabaseTemplate::Template0`] or a matching named template.
+/// Settings given here override the encoding and locale given to [`DatabaseTemplate::Template0`].
+#[derive(Default)]
+pub struct DatabaseLocale {
+    /// Encoding, such as ``UTF8``
+    pub encoding: Option<String>,
+    /// Collation order (``LC_COLLATE``), such as ``C``
+    pub lc_collate: Option<String>,
+    /// Character classification (``LC_CTYPE``), such as ``C``
+    pub lc_ctype: Option<String>,
+}
+
+impl DatabaseLocale {
+    fn clauses(&self) -> String {
+        postgres::database_locale_clauses(
+            self.encoding.as_deref(),
+            self.lc_collate.as_deref(),
+            self.lc_ctype.as_deref(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DatabaseLocale, DatabaseTemplate};
+
+    fn template0() -> DatabaseTemplate {
+        DatabaseTemplate::Template0 {
+            encoding: "UTF8".to_owned(),
+            locale: "C".to_owned(),
+        }
+    }
+
+    #[test]
+    fn template0_keeps_own_settings_without_overrides() {
+        assert_eq!(
+            template0().create_database("db_pool_db", &DatabaseLocale::default()),
+            "CREATE DATABASE db_pool_db TEMPLATE template0 ENCODING 'UTF8' LOCALE 'C'"
+        );
+    }
+
+    #[test]
+    fn template0_settings_are_overridden_once() {
+        let locale = DatabaseLocale {
+            encoding: Some("LATIN1".to_owned()),
+            lc_collate: Some("POSIX".to_owned()),
+            lc_ctype: None,
+        };
+        assert_eq!(
+            template0().create_database("db_pool_db", &locale),
+            "CREATE DATABASE db_pool_db TEMPLATE template0 ENCODING 'LATIN1' LC_COLLATE 'POSIX' LC_CTYPE 'C'"
+        );
+    }
+
+    #[test]
+    fn named_template_appends_set_settings() {
+        let locale = DatabaseLocale {
+            lc_collate: Some("C".to_owned()),
+            ..DatabaseLocale::default()
+        };
+        assert_eq!(
+            DatabaseTemplate::Named("template_db".to_owned())
+                .create_database("db_pool_db", &locale),
+            "CREATE DATABASE db_pool_db TEMPLATE template_db LC_COLLATE 'C'"
+        );
+    }
+}
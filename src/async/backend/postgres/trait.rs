@@ -3,14 +3,36 @@ use std::{
     fmt::Debug,
     marker::PhantomData,
     ops::{Deref, DerefMut},
+    panic::{AssertUnwindSafe, resume_unwind},
+    time::{Duration, SystemTime},
 };
 
 use async_trait::async_trait;
+use futures::{FutureExt, TryStreamExt};
+use tokio::{
+    sync::Mutex as AsyncMutex,
+    time::{Instant, sleep},
+};
 use uuid::Uuid;
 
-use crate::{common::statement::postgres, util::get_db_name};
+use crate::{
+    common::statement::postgres,
+    util::{get_prefixed_db_name, get_snapshot_db_name, is_generated_db_name, is_prefixed_db_name},
+};
+
+use super::{
+    super::error::{Error as BackendError, Operation},
+    breaker::CircuitBreaker,
+    maintenance::MaintenanceMode,
+    naming::DbNameGenerator,
+    observer::{Observer, Phase},
+    privileges::RestrictedPrivileges,
+    role::RoleOptions,
+    template::{DatabaseLocale, DatabaseTemplate},
+    truncate::{FkHandling, TruncateMode},
+};
 
-use super::super::error::Error as BackendError;
+const DROP_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 #[async_trait]
 pub(super) trait PostgresBackend<'pool>: Send + Sync + 'static {
@@ -68,6 +90,9 @@ pub(super) trait PostgresBackend<'pool>: Send + Sync + 'static {
         &self,
         db_id: Uuid,
     ) -> Result<Self::Connection, Self::ConnectionError>;
+    async fn establish_privileged_default_connection(
+        &self,
+    ) -> Result<Self::Connection, Self::ConnectionError>;
     async fn establish_restricted_database_connection(
         &self,
         db_id: Uuid,
@@ -79,15 +104,128 @@ pub(super) trait PostgresBackend<'pool>: Send + Sync + 'static {
         &self,
         conn: &mut Self::Connection,
     ) -> Result<Vec<String>, Self::QueryError>;
-    async fn create_entities(&self, conn: Self::Connection) -> Option<Self::Connection>;
+    async fn get_previous_database_comments(
+        &self,
+        conn: &mut Self::Connection,
+    ) -> Result<Vec<(String, Option<String>)>, Self::QueryError>;
+    async fn create_entities(
+        &self,
+        db_id: Uuid,
+        stage: usize,
+        conn: Self::Connection,
+    ) -> Result<Option<Self::Connection>, Self::QueryError>;
+    async fn create_entities_with_pool(&self, db_id: Uuid, pool: Self::Pool);
     async fn create_connection_pool(&self, db_id: Uuid) -> Result<Self::Pool, Self::BuildError>;
+    async fn create_privileged_connection_pool(
+        &self,
+        db_id: Uuid,
+    ) -> Result<Self::Pool, Self::BuildError>;
+    async fn warm_up_pool(&self, pool: &Self::Pool, count: u32) -> Result<(), Self::PoolError>;
+    async fn before_drop(&self, db_id: Uuid, conn: Self::Connection);
+    async fn on_init(&self, conn: Self::Connection);
 
     async fn get_table_names(
         &self,
         privileged_conn: &mut Self::Connection,
-    ) -> Result<Vec<String>, Self::QueryError>;
+    ) -> Result<Vec<(String, String)>, Self::QueryError>;
+    async fn get_partition_names(
+        &self,
+        privileged_conn: &mut Self::Connection,
+    ) -> Result<Vec<(String, String)>, Self::QueryError>;
+    async fn get_sequence_names(
+        &self,
+        privileged_conn: &mut Self::Connection,
+    ) -> Result<Vec<(String, String)>, Self::QueryError>;
+    async fn get_role_attributes(
+        &self,
+        privileged_conn: &mut Self::Connection,
+    ) -> Result<RoleAttributes, Self::QueryError>;
 
     fn get_drop_previous_databases(&self) -> bool;
+    fn get_drop_previous_databases_concurrency(&self) -> Option<usize>;
+    fn get_create_entities_stages(&self) -> usize;
+    fn get_analyze_after_create(&self) -> bool;
+    fn get_role_validity(&self) -> Option<Duration>;
+    fn get_drop_grace_period(&self) -> Option<Duration>;
+    fn get_database_template(&self) -> &DatabaseTemplate;
+    fn has_before_drop(&self) -> bool;
+    fn has_on_init(&self) -> bool;
+    fn has_create_entities_with_pool(&self) -> bool;
+    fn get_database_owner(&self) -> Option<&str>;
+    fn get_excluded_tables(&self) -> &[String];
+    fn get_entity_template_id(&self) -> Option<Uuid>;
+    fn get_verify_query(&self) -> Option<&str>;
+    fn get_db_name_prefix(&self) -> &str;
+    fn get_db_name(&self, db_id: Uuid) -> String {
+        self.get_db_name_generator()
+            .and_then(|generator| generator.name(db_id))
+            .unwrap_or_else(|| get_prefixed_db_name(self.get_db_name_prefix(), db_id))
+    }
+    fn get_connection_retries(&self) -> Option<(usize, Duration)>;
+    fn get_restricted_privileges(&self) -> &RestrictedPrivileges;
+    fn get_extensions(&self) -> &[String];
+    fn get_maintenance_mode(&self) -> &MaintenanceMode;
+    fn get_clean_batch_size(&self) -> usize;
+    fn get_truncate_mode(&self) -> &TruncateMode;
+    fn get_dry_run(&self) -> bool;
+    fn get_restricted_password(&self, db_id: Uuid) -> String;
+    fn get_reassign_owned_roles(&self) -> &[String];
+    fn get_clean_schemas(&self) -> &[String];
+    fn get_fk_handling(&self) -> &FkHandling;
+    fn get_observer(&self) -> Option<&dyn Observer>;
+    fn get_warn_previous_databases(&self) -> bool;
+    fn get_statement_timeout(&self) -> Option<Duration>;
+    fn get_unrestricted_database_owner(&self) -> Option<&str>;
+    fn get_terminate_connections_before_drop(&self) -> bool;
+    fn get_connection_statements(&self) -> &[String];
+    fn get_db_name_generator(&self) -> Option<&DbNameGenerator>;
+    fn get_max_create_retries(&self) -> usize;
+    fn get_truncate_partition_parents_only(&self) -> bool;
+    fn get_previous_databases_ttl(&self) -> Option<Duration>;
+    fn get_warm_up_pools(&self) -> bool;
+    fn get_restricted_min_idle(&self) -> Option<u32>;
+    fn get_clean_table_statements(&self, table_name: &str) -> Option<Vec<String>>;
+    fn get_create_jitter(&self) -> Option<Duration>;
+    fn get_role_creation_lock(&self) -> Option<&AsyncMutex<()>>;
+    fn get_create_entities_in_transaction(&self) -> bool;
+    fn get_drop_lock_timeout(&self) -> Option<Duration>;
+    fn get_database_locale(&self) -> &DatabaseLocale;
+    fn get_role_options(&self) -> &RoleOptions;
+    fn get_shared_role(&self) -> Option<&str>;
+    fn get_role_name(&self, db_id: Uuid) -> String {
+        self.get_shared_role()
+            .map_or_else(|| self.get_db_name(db_id), ToOwned::to_owned)
+    }
+    fn get_default_pool_breaker(&self) -> Option<&CircuitBreaker>;
+}
+
+/// Attributes of the privileged role relevant to creating databases
+pub(super) struct RoleAttributes {
+    pub(super) superuser: bool,
+    pub(super) create_db: bool,
+    pub(super) create_role: bool,
+}
+
+impl RoleAttributes {
+    fn can_create_databases(&self) -> bool {
+        self.superuser || self.create_db
+    }
+
+    fn missing_privileges(&self) -> Vec<String> {
+        // Superusers bypass all permission checks
+        if self.superuser {
+            return Vec::new();
+        }
+
+        [
+            ("CREATEDB", self.create_db),
+            ("CREATEROLE", self.create_role),
+        ]
+        .into_iter()
+        .filter(|(_, granted)| !granted)
+        .map(|(privilege, _)| privilege.to_owned())
+        .collect()
+    }
 }
 
 pub(super) struct PostgresBackendWrapper<'backend, 'pool, B: PostgresBackend<'pool>> {
@@ -117,453 +255,3375 @@ where
     'backend: 'pool,
     B: PostgresBackend<'pool>,
 {
+    async fn execute_statement(
+        &'backend self,
+        query: &str,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Attach failed statement to query error
+        self.execute_query(query, conn)
+            .await
+            .map_err(|err| BackendError::Statement(query.to_owned(), err))
+    }
+
+    async fn acquire_default_connection(
+        &'backend self,
+        operation: Operation,
+    ) -> Result<
+        B::PooledConnection,
+        BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        // Fast-fail while the default pool keeps failing if circuit breaker is configured
+        let result = match self.get_default_pool_breaker() {
+            Some(breaker) => breaker.call(self.get_default_connection()).await,
+            None => self.get_default_connection().await.map_err(Some),
+        };
+        result.map_err(|err| match err {
+            Some(err) => BackendError::OperationPool(operation, err),
+            None => BackendError::CircuitOpen(operation),
+        })
+    }
+
+    async fn batch_execute_statements<'a>(
+        &'backend self,
+        query: impl IntoIterator<Item = Cow<'a, str>> + Send,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Attach failed statements to query error
+        let query = query.into_iter().collect::<Vec<_>>();
+        self.batch_execute_query(query.clone(), conn)
+            .await
+            .map_err(|err| BackendError::Statement(query.join(";"), err))
+    }
+
+    pub(super) async fn verify(
+        &'backend self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get connection to default database as privileged user
+        let conn = &mut self.get_default_connection().await.map_err(Into::into)?;
+
+        // Report privileges missing for creating databases and their roles
+        let missing_privileges = self
+            .get_role_attributes(conn)
+            .await
+            .map_err(Into::into)?
+            .missing_privileges();
+        if missing_privileges.is_empty() {
+            Ok(())
+        } else {
+            Err(BackendError::MissingPrivileges(missing_privileges))
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        allow(clippy::complexity),
+        tracing::instrument(name = "db_pool.init", skip_all)
+    )]
     pub(super) async fn init(
         &'backend self,
     ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
     {
-        // Drop previous databases if needed
-        if self.get_drop_previous_databases() {
+        // Drop previous databases if needed, only logging them in warn-only mode
+        let warn_previous_databases = self.get_warn_previous_databases();
+        if self.get_drop_previous_databases() || warn_previous_databases {
             // Get connection to default database as privileged user
-            let conn = &mut self.get_default_connection().await.map_err(Into::into)?;
+            let conn = &mut self
+                .acquire_default_connection(Operation::DropPreviousDatabases)
+                .await?;
 
-            // Get previous database names
-            let db_names = self
+            // Get previous database names, skipping databases with other prefixes
+            // and matching generated names by their shape if names are generated
+            let mut db_names = self
                 .get_previous_database_names(conn)
                 .await
                 .map_err(Into::into)?;
+            let prefix = self.get_db_name_prefix();
+            let generates_names = self.get_db_name_generator().is_some();
+            db_names.retain(|db_name| {
+                is_prefixed_db_name(prefix, db_name)
+                    || (generates_names && is_generated_db_name(prefix, db_name))
+            });
 
-            // Drop databases
-            let futures = db_names
-                .iter()
-                .map(|db_name| async move {
-                    let conn = &mut self.get_default_connection().await.map_err(Into::into)?;
-                    self.execute_query(postgres::drop_database(db_name.as_str()).as_str(), conn)
-                        .await
-                        .map_err(Into::into)?;
-                    Ok::<
-                        _,
-                        BackendError<
-                            B::BuildError,
-                            B::PoolError,
-                            B::ConnectionError,
-                            B::QueryError,
-                        >,
-                    >(())
-                })
-                .collect::<Vec<_>>();
-            futures::future::try_join_all(futures).await?;
+            // Keep databases created within TTL, treating databases of unknown age as expired
+            if let Some(ttl) = self.get_previous_databases_ttl() {
+                let comments = self
+                    .get_previous_database_comments(conn)
+                    .await
+                    .map_err(Into::into)?;
+                let now = SystemTime::now();
+                db_names.retain(|db_name| {
+                    comments
+                        .iter()
+                        .find(|(name, _)| name == db_name)
+                        .and_then(|(_, comment)| comment.as_deref())
+                        .and_then(postgres::parse_database_creation_time)
+                        .is_none_or(|created_at| {
+                            now.duration_since(created_at).is_ok_and(|age| age > ttl)
+                        })
+                });
+            }
+
+            if warn_previous_databases {
+                // Leave databases behind, reporting them instead
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    count = db_names.len(),
+                    ?db_names,
+                    "leaving previous databases that would be dropped"
+                );
+            } else {
+                // Drop databases, concurrently up to limit if set,
+                // tolerating databases already dropped by other processes
+                futures::stream::iter(db_names.iter().map(Ok))
+                    .try_for_each_concurrent(
+                        self.get_drop_previous_databases_concurrency(),
+                        |db_name| async move {
+                            let conn = &mut self
+                                .acquire_default_connection(Operation::DropPreviousDatabases)
+                                .await?;
+                            self.execute_statement(
+                                postgres::drop_database_if_exists(db_name.as_str()).as_str(),
+                                conn,
+                            )
+                            .await?;
+                            Ok::<
+                                _,
+                                BackendError<
+                                    B::BuildError,
+                                    B::PoolError,
+                                    B::ConnectionError,
+                                    B::QueryError,
+                                >,
+                            >(())
+                        },
+                    )
+                    .await?;
+            }
+        }
+
+        // Run one-time hook with privileged connection to default database if needed
+        if self.has_on_init() && !self.get_dry_run() {
+            let conn = self
+                .establish_privileged_default_connection()
+                .await
+                .map_err(Into::into)?;
+            self.on_init(conn).await;
+        }
+
+        // Create entities once in entity template database if needed
+        if let Some(template_id) = self.get_entity_template_id() {
+            self.generate_db_name(template_id)
+                .map_err(BackendError::InvalidDbName)?;
+            let template_name = self.get_db_name(template_id);
+            let template_name = template_name.as_str();
+
+            // Get connection to default database as privileged user
+            let conn = &mut self
+                .acquire_default_connection(Operation::CreateEntityTemplate)
+                .await?;
+
+            // Create entity template database from template
+            self.create_database(template_name, None, conn).await?;
+
+            // Grant database ownership to owner role if different from privileged role
+            if let Some(owner) = self.get_database_owner() {
+                self.execute_statement(
+                    postgres::grant_database_ownership(template_name, owner).as_str(),
+                    conn,
+                )
+                .await?;
+            }
+
+            // Create entities, closing connection so that the template can be cloned
+            drop(self.create_privileged_entities(template_id).await?);
         }
 
         Ok(())
     }
 
-    pub(super) async fn create(
+    async fn create_database(
         &'backend self,
-        db_id: Uuid,
-        restrict_privileges: bool,
-    ) -> Result<B::Pool, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+        db_name: &str,
+        entity_template: Option<&str>,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
     {
-        // Get database name based on UUID
-        let db_name = get_db_name(db_id);
-        let db_name = db_name.as_str();
-
-        // Get connection to default database as privileged user
-        let default_conn = &mut self.get_default_connection().await.map_err(Into::into)?;
-
-        // Create database
-        self.execute_query(postgres::create_database(db_name).as_str(), default_conn)
-            .await
-            .map_err(Into::into)?;
+        // Clone entity template if given, otherwise create database from configured template
+        let create_database = match entity_template {
+            Some(template) => {
+                postgres::create_database_from_template(db_name, template, None, None)
+            }
+            None => self
+                .get_database_template()
+                .create_database(db_name, self.get_database_locale()),
+        };
+        if let Err(err) = self.execute_query(create_database.as_str(), conn).await {
+            // Report name collision if database already exists
+            let db_names = self
+                .get_previous_database_names(conn)
+                .await
+                .map_err(Into::into)?;
+            if db_names.iter().any(|name| name == db_name) {
+                return Err(BackendError::DatabaseExists(db_name.to_owned()));
+            }
 
-        // Create role
-        self.execute_query(postgres::create_role(db_name).as_str(), default_conn)
-            .await
-            .map_err(Into::into)?;
+            // Report missing privilege if privileged role cannot create databases
+            if !self
+                .get_role_attributes(conn)
+                .await
+                .map_err(Into::into)?
+                .can_create_databases()
+            {
+                return Err(BackendError::MissingPrivileges(vec!["CREATEDB".to_owned()]));
+            }
 
-        if restrict_privileges {
-            // Connect to database as privileged user
-            let establish_connection = || async {
-                self.establish_privileged_database_connection(db_id)
-                    .await
-                    .map_err(Into::into)
-            };
+            return Err(BackendError::Statement(create_database, err));
+        }
 
-            let conn = establish_connection().await?;
+        self.record_creation_time(db_name, conn).await
+    }
 
-            // Create entities as privileged user and get back connection if possible
-            let mut conn = match self.create_entities(conn).await {
-                None => establish_connection().await?,
-                Some(conn) => conn,
-            };
+    async fn record_creation_time(
+        &'backend self,
+        db_name: &str,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Store creation time for initializations dropping only expired databases
+        self.execute_statement(
+            postgres::comment_database_creation_time(db_name, SystemTime::now()).as_str(),
+            conn,
+        )
+        .await
+    }
 
-            // Grant table privileges to restricted role
-            self.execute_query(
-                postgres::grant_restricted_table_privileges(db_name).as_str(),
-                &mut conn,
-            )
+    async fn establish_restricted_connection(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<
+        B::Connection,
+        BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        let mut conn = self
+            .retry_connection(|| self.establish_restricted_database_connection(db_id))
             .await
             .map_err(Into::into)?;
 
-            // Grant sequence privileges to restricted role
-            self.execute_query(
-                postgres::grant_restricted_sequence_privileges(db_name).as_str(),
-                &mut conn,
-            )
-            .await
-            .map_err(Into::into)?;
+        // Apply configured per-connection setup
+        let stmts = self.get_connection_statements();
+        if !stmts.is_empty() {
+            self.batch_execute_statements(stmts.iter().map(|stmt| stmt.as_str().into()), &mut conn)
+                .await?;
+        }
 
-            // Store database connection for reuse when cleaning
-            self.put_database_connection(db_id, conn);
-        } else {
-            // Grant database ownership to database-unrestricted role
-            self.execute_query(
-                postgres::grant_database_ownership(db_name, db_name).as_str(),
-                default_conn,
+        Ok(conn)
+    }
+
+    async fn set_database_parameters(
+        &'backend self,
+        db_name: &str,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        if let Some(statement_timeout) = self.get_statement_timeout() {
+            let statement_timeout = format!("{}ms", statement_timeout.as_millis());
+            self.execute_statement(
+                postgres::set_database_parameter(
+                    db_name,
+                    "statement_timeout",
+                    statement_timeout.as_str(),
+                )
+                .as_str(),
+                conn,
             )
-            .await
-            .map_err(Into::into)?;
+            .await?;
+        }
 
-            // Connect to database as database-unrestricted user
-            let conn = self
-                .establish_restricted_database_connection(db_id)
-                .await
-                .map_err(Into::into)?;
+        Ok(())
+    }
 
-            // Create entities as database-unrestricted user
-            let _ = self.create_entities(conn).await;
-        }
+    async fn retry_connection<T, E, F>(&self, establish: impl Fn() -> F) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        // Establish connection once if retries are disabled
+        let Some((attempts, base_delay)) = self.get_connection_retries() else {
+            return establish().await;
+        };
 
-        // Create connection pool with attached role
-        let pool = self
-            .create_connection_pool(db_id)
-            .await
-            .map_err(Into::into)?;
+        // Retry with exponential backoff, returning last error after final attempt
+        let mut delay = base_delay;
+        for _ in 1..attempts {
+            if let Ok(value) = establish().await {
+                return Ok(value);
+            }
+            sleep(delay).await;
+            delay *= 2;
+        }
+        establish().await
+    }
 
-        Ok(pool)
+    fn observe<T, E: Debug>(
+        &self,
+        phase: Phase,
+        db_id: Uuid,
+        start: Instant,
+        result: &Result<T, E>,
+    ) {
+        // Report lifecycle phase to observer if any
+        let Some(observer) = self.get_observer() else {
+            return;
+        };
+        match result {
+            Ok(_) => {
+                let duration = start.elapsed();
+                match phase {
+                    Phase::Create => observer.on_create(db_id, duration),
+                    Phase::Clean => observer.on_clean(db_id, duration),
+                    Phase::Drop => observer.on_drop(db_id, duration),
+                }
+            }
+            Err(err) => observer.on_error(phase, err),
+        }
     }
 
-    pub(super) async fn clean(
+    async fn create_extensions(
         &'backend self,
         db_id: Uuid,
     ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
     {
-        // Get privileged connection to database
-        let mut conn = self.get_database_connection(db_id);
-
-        // Get table names
-        let table_names = self.get_table_names(&mut conn).await.map_err(Into::into)?;
-
-        // Generate truncate statements
-        let stmts = table_names
-            .iter()
-            .map(|table_name| postgres::truncate_table(table_name.as_str()).into());
+        // Skip connecting to database never created in dry run
+        let extensions = self.get_extensions();
+        if extensions.is_empty() || self.get_dry_run() {
+            return Ok(());
+        }
 
-        // Truncate tables
-        self.batch_execute_query(stmts, &mut conn)
+        // Connect to database as privileged user
+        let mut conn = self
+            .retry_connection(|| self.establish_privileged_database_connection(db_id))
             .await
             .map_err(Into::into)?;
 
-        // Store database connection back for reuse
-        self.put_database_connection(db_id, conn);
+        self.batch_execute_statements(
+            extensions
+                .iter()
+                .map(|extension| postgres::create_extension(extension).into()),
+            &mut conn,
+        )
+        .await
+    }
 
-        Ok(())
+    async fn get_cleaned_table_names(
+        &'backend self,
+        conn: &mut B::Connection,
+    ) -> Result<
+        Vec<(String, String)>,
+        BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        let table_names = self.get_table_names(conn).await.map_err(Into::into)?;
+
+        // Skip partitions truncated along with their parents
+        let partition_names = if self.get_truncate_partition_parents_only() {
+            self.get_partition_names(conn).await.map_err(Into::into)?
+        } else {
+            Vec::new()
+        };
+
+        // Keep tables in configured schemas, if any
+        let schema_names = self.get_clean_schemas();
+        Ok(table_names
+            .into_iter()
+            .filter(|(schema_name, _)| {
+                schema_names.is_empty() || schema_names.contains(schema_name)
+            })
+            .filter(|table_name| !partition_names.contains(table_name))
+            .collect())
     }
 
-    pub(super) async fn drop(
+    async fn reassign_owned(
         &'backend self,
         db_id: Uuid,
-        is_restricted: bool,
     ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
     {
-        // Drop privileged connection to database
-        if is_restricted {
-            self.get_database_connection(db_id);
+        let roles = self.get_reassign_owned_roles();
+        if roles.is_empty() {
+            return Ok(());
         }
 
-        // Get database name based on UUID
-        let db_name = get_db_name(db_id);
-        let db_name = db_name.as_str();
-
-        // Get connection to default database as privileged user
-        let conn = &mut self.get_default_connection().await.map_err(Into::into)?;
+        let role_name = self.get_role_name(db_id);
 
-        // Drop database
-        self.execute_query(postgres::drop_database(db_name).as_str(), conn)
+        // Connect to database as privileged user
+        let mut conn = self
+            .retry_connection(|| self.establish_privileged_database_connection(db_id))
             .await
             .map_err(Into::into)?;
 
-        // Drop attached role
-        self.execute_query(postgres::drop_role(db_name).as_str(), conn)
-            .await
-            .map_err(Into::into)?;
+        self.batch_execute_statements(
+            roles
+                .iter()
+                .map(|role| postgres::reassign_owned(role, role_name.as_str()).into()),
+            &mut conn,
+        )
+        .await
+    }
+
+    async fn grant_unrestricted_ownership(
+        &'backend self,
+        db_name: &str,
+        role_name: &str,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        let owner = self.get_unrestricted_database_owner().unwrap_or(role_name);
+        self.execute_statement(
+            postgres::grant_database_ownership(db_name, owner).as_str(),
+            conn,
+        )
+        .await?;
+
+        // Let database-unrestricted role act as configured owner role
+        if owner != role_name {
+            self.execute_statement(postgres::grant_role(owner, role_name).as_str(), conn)
+                .await?;
+        }
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-pub(super) mod tests {
-    #![allow(clippy::unwrap_used)]
+    async fn wait_create_jitter(&'backend self, db_id: Uuid) {
+        // Derive delay from random database UUID, spreading out concurrent creations
+        if let Some(jitter) = self.get_create_jitter() {
+            let permille = u32::try_from(db_id.as_u128() % 1000).unwrap_or_default();
+            sleep(jitter * permille / 1000).await;
+        }
+    }
 
-    use bb8::Pool as Bb8Pool;
-    use diesel::{dsl::exists, insert_into, prelude::*, select, sql_query, table};
-    use diesel_async::{
-        AsyncPgConnection, RunQueryDsl, pooled_connection::AsyncDieselConnectionManager,
-    };
-    use futures::{
-        Future,
-        future::{join_all, try_join_all},
-    };
-    use tokio::sync::OnceCell;
-    use uuid::Uuid;
+    async fn create_role(
+        &'backend self,
+        db_id: Uuid,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Create one role at a time if serialized
+        let _guard = match self.get_role_creation_lock() {
+            Some(lock) => Some(lock.lock().await),
+            None => None,
+        };
 
-    use crate::{
-        r#async::{backend::r#trait::Backend, db_pool::DatabasePoolBuilder},
-        common::statement::postgres::tests::{DDL_STATEMENTS, DML_STATEMENTS},
-        tests::{PG_DROP_LOCK, get_privileged_postgres_config},
-        util::get_db_name,
-    };
+        // Create role with configured password and attributes, expiring after validity period if needed
+        let db_name = self.get_db_name(db_id);
+        let password = self.get_restricted_password(db_id);
+        let valid_until = self
+            .get_role_validity()
+            .map(|validity| SystemTime::now() + validity);
+        self.execute_statement(
+            self.get_role_options()
+                .create_statement(db_name.as_str(), password.as_str(), valid_until)
+                .as_str(),
+            conn,
+        )
+        .await
+    }
 
-    pub type Pool = Bb8Pool<AsyncDieselConnectionManager<AsyncPgConnection>>;
+    async fn create_privileged_entities(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<
+        B::Connection,
+        BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        // Connect to database as privileged user
+        let establish_connection = || async {
+            let conn = self
+                .retry_connection(|| self.establish_privileged_database_connection(db_id))
+                .await
+                .map_err(Into::into)?;
+            self.begin_entities_transaction(conn).await
+        };
 
-    table! {
-        pg_database (oid) {
-            oid -> Int4,
-            datname -> Text
+        let mut conn = establish_connection().await?;
+
+        // Create entities in stages as privileged user and get back connection if possible
+        for stage in 0..self.get_create_entities_stages() {
+            conn = match self
+                .create_entities(db_id, stage, conn)
+                .await
+                .map_err(Into::into)?
+            {
+                // Fail rather than resume on a fresh connection, the transaction having been rolled back
+                None if self.get_create_entities_in_transaction() => {
+                    return Err(BackendError::StageConnectionDropped(stage));
+                }
+                None => establish_connection().await?,
+                Some(conn) => conn,
+            };
         }
+
+        self.commit_entities_transaction(&mut conn).await?;
+
+        Ok(conn)
     }
 
-    table! {
-        book (id) {
-            id -> Int4,
-            title -> Text
+    async fn begin_entities_transaction(
+        &'backend self,
+        mut conn: B::Connection,
+    ) -> Result<
+        B::Connection,
+        BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        // Begin transaction spanning entity creation stages if needed
+        if self.get_create_entities_in_transaction() {
+            self.execute_statement(postgres::BEGIN, &mut conn).await?;
         }
+
+        Ok(conn)
     }
 
-    #[allow(unused_variables)]
-    pub trait PgDropLock<T>
-    where
-        Self: Future<Output = T> + Sized,
+    async fn commit_entities_transaction(
+        &'backend self,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
     {
-        async fn lock_drop(self) -> T {
-            let guard = PG_DROP_LOCK.write().await;
-            self.await
+        // Commit entities only once all stages have succeeded, dropped connections rolling back
+        if self.get_create_entities_in_transaction() {
+            self.execute_statement(postgres::COMMIT, conn).await?;
         }
 
-        async fn lock_read(self) -> T {
-            let guard = PG_DROP_LOCK.read().await;
-            self.await
+        Ok(())
+    }
+
+    async fn create_pool_entities(
+        &'backend self,
+        db_id: Uuid,
+        privileged: bool,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        if !self.has_create_entities_with_pool() {
+            return Ok(());
         }
+
+        // Create connection pool as privileged or database-unrestricted user
+        let pool = if privileged {
+            self.retry_connection(|| self.create_privileged_connection_pool(db_id))
+                .await
+        } else {
+            self.retry_connection(|| self.create_connection_pool(db_id))
+                .await
+        }
+        .map_err(Into::into)?;
+
+        // Create entities over pool, dropping it afterwards
+        self.create_entities_with_pool(db_id, pool).await;
+
+        Ok(())
     }
 
-    impl<T, F> PgDropLock<T> for F where F: Future<Output = T> + Sized {}
+    pub(super) async fn create(
+        &'backend self,
+        db_id: Uuid,
+        restrict_privileges: bool,
+    ) -> Result<B::Pool, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        self.observe_create(db_id, || self.try_create(db_id, restrict_privileges))
+            .await
+    }
 
-    async fn get_privileged_connection_pool() -> &'static Pool {
-        static POOL: OnceCell<Pool> = OnceCell::const_new();
-        POOL.get_or_init(|| async {
-            let config = get_privileged_postgres_config();
-            let connection_url = config.default_connection_url();
-            let manager = AsyncDieselConnectionManager::new(connection_url);
-            Bb8Pool::builder().build(manager).await.unwrap()
+    pub(super) async fn create_connection(
+        &'backend self,
+        db_id: Uuid,
+        restrict_privileges: bool,
+    ) -> Result<
+        B::Connection,
+        BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        self.observe_create(db_id, || {
+            self.try_create_connection(db_id, restrict_privileges)
         })
         .await
     }
 
-    async fn create_restricted_connection_pool(db_name: &str) -> Pool {
-        let config = get_privileged_postgres_config();
-        let connection_url =
-            config.restricted_database_connection_url(db_name, Some(db_name), db_name);
-        let manager = AsyncDieselConnectionManager::new(connection_url);
-        Bb8Pool::builder().build(manager).await.unwrap()
+    async fn observe_create<T, F>(
+        &'backend self,
+        db_id: Uuid,
+        create: impl Fn() -> F,
+    ) -> Result<T, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    where
+        F: Future<
+            Output = Result<
+                T,
+                BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+            >,
+        >,
+    {
+        let start = Instant::now();
+
+        let mut retries = 0;
+        let result = loop {
+            let result = self.create_or_discard(db_id, create()).await;
+
+            // Retry with a newly generated name if the generated name is already taken,
+            // e.g. by a concurrent creation generating the same name
+            match (&result, self.get_db_name_generator()) {
+                (Err(BackendError::DatabaseExists(_)), Some(generator))
+                    if retries < self.get_max_create_retries() =>
+                {
+                    generator.forget(db_id);
+                    retries += 1;
+                }
+                _ => break result,
+            }
+        };
+
+        self.observe(Phase::Create, db_id, start, &result);
+        result
     }
 
-    async fn create_database(conn: &mut AsyncPgConnection) -> String {
-        let db_id = Uuid::new_v4();
-        let db_name = get_db_name(db_id);
-        sql_query(format!("CREATE DATABASE {db_name}"))
-            .execute(conn)
-            .await
-            .unwrap();
-        db_name
+    async fn create_or_discard<T>(
+        &'backend self,
+        db_id: Uuid,
+        create: impl Future<
+            Output = Result<
+                T,
+                BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+            >,
+        >,
+    ) -> Result<T, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Generate database name upfront, failing before anything is created if it is invalid
+        let result = match self
+            .generate_db_name(db_id)
+            .map_err(BackendError::InvalidDbName)
+        {
+            Ok(()) => {
+                // Discard half-created database and role if creating entities panics
+                match AssertUnwindSafe(create).catch_unwind().await {
+                    Ok(result) => result,
+                    Err(panic) => {
+                        self.discard(db_id).await;
+                        resume_unwind(panic);
+                    }
+                }
+            }
+            Err(err) => Err(err),
+        };
+
+        // Discard them as well if creation fails, unless the name belongs to another database
+        // or nothing was attempted while the circuit breaker is open or the name is invalid
+        if let Err(err) = &result
+            && !matches!(
+                err,
+                BackendError::DatabaseExists(_)
+                    | BackendError::CircuitOpen(_)
+                    | BackendError::InvalidDbName(_)
+            )
+        {
+            self.discard(db_id).await;
+        }
+
+        result
     }
 
-    async fn create_databases(count: i64, pool: &Pool) -> Vec<String> {
-        let futures = (0..count)
-            .map(|_| async {
-                let conn = &mut pool.get().await.unwrap();
-                create_database(conn).await
-            })
-            .collect::<Vec<_>>();
-        join_all(futures).await
+    fn generate_db_name(&'backend self, db_id: Uuid) -> Result<(), String> {
+        // Remember generated name for subsequent lookups if valid, failing with it otherwise
+        if let Some(generator) = self.get_db_name_generator() {
+            generator.generate(self.get_db_name_prefix(), db_id)?;
+        }
+        Ok(())
     }
 
-    async fn count_databases(db_names: &Vec<String>, conn: &mut AsyncPgConnection) -> i64 {
-        pg_database::table
-            .filter(pg_database::datname.eq_any(db_names))
-            .count()
-            .get_result(conn)
+    async fn discard(&'backend self, db_id: Uuid) {
+        let db_name = self.get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        // Clean up on a best-effort basis since the panic or error is propagated anyway,
+        // bypassing the circuit breaker so that an open breaker does not leave the database behind
+        if let Ok(mut conn) = self.get_default_connection().await {
+            for stmt in [
+                postgres::force_drop_database_if_exists(db_name),
+                postgres::drop_role_if_exists(db_name),
+            ] {
+                self.execute_statement(stmt.as_str(), &mut conn).await.ok();
+            }
+        }
+
+        // Release generated name
+        if let Some(generator) = self.get_db_name_generator() {
+            generator.forget(db_id);
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        allow(clippy::complexity),
+        tracing::instrument(
+            name = "db_pool.create",
+            skip_all,
+            fields(%db_id, db_name = %self.get_db_name(db_id), restrict_privileges)
+        )
+    )]
+    async fn try_create_database(
+        &'backend self,
+        db_id: Uuid,
+        restrict_privileges: bool,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get database name based on UUID
+        let db_name = self.get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        self.wait_create_jitter(db_id).await;
+
+        // Get connection to default database as privileged user
+        let default_conn = &mut self
+            .acquire_default_connection(Operation::Create(db_name.to_owned()))
+            .await?;
+
+        // Create database, cloning entity template for restricted databases if enabled
+        let entity_template = self
+            .get_entity_template_id()
+            .filter(|_| restrict_privileges)
+            .map(|template_id| self.get_db_name(template_id));
+        self.create_database(db_name, entity_template.as_deref(), default_conn)
+            .await?;
+
+        // Apply configured parameters to every subsequent connection to database
+        self.set_database_parameters(db_name, default_conn).await?;
+
+        // Create extensions as privileged user before any entities are created
+        self.create_extensions(db_id).await?;
+
+        // Create role attached to database unless sharing a pre-existing role
+        if self.get_shared_role().is_none() {
+            self.create_role(db_id, default_conn).await?;
+        }
+        let role_name = self.get_role_name(db_id);
+        let role_name = role_name.as_str();
+
+        // Skip connecting to database never created in dry run
+        if self.get_dry_run() {
+            return Ok(());
+        }
+
+        if restrict_privileges {
+            // Grant database ownership to owner role if different from privileged role
+            if let Some(owner) = self.get_database_owner() {
+                self.execute_statement(
+                    postgres::grant_database_ownership(db_name, owner).as_str(),
+                    default_conn,
+                )
+                .await?;
+            }
+
+            // Create entities as privileged user unless cloned from entity template
+            let mut conn = if entity_template.is_some() {
+                self.retry_connection(|| self.establish_privileged_database_connection(db_id))
+                    .await
+                    .map_err(Into::into)?
+            } else {
+                let conn = self.create_privileged_entities(db_id).await?;
+                self.create_pool_entities(db_id, true).await?;
+                conn
+            };
+
+            // Grant configured privileges to restricted role
+            for stmt in self.get_restricted_privileges().grant_statements(
+                db_name,
+                role_name,
+                self.get_clean_schemas(),
+            ) {
+                self.execute_statement(stmt.as_str(), &mut conn).await?;
+            }
+
+            // Collect statistics for the created entities if needed
+            if self.get_analyze_after_create() {
+                self.execute_statement(postgres::ANALYZE, &mut conn).await?;
+            }
+
+            // Store database connection for reuse when cleaning
+            self.put_database_connection(db_id, conn);
+        } else {
+            // Grant database ownership to configured owner role or database-unrestricted role
+            self.grant_unrestricted_ownership(db_name, role_name, default_conn)
+                .await?;
+
+            // Connect to database as database-unrestricted user
+            let establish_connection = || self.establish_restricted_connection(db_id);
+
+            let establish_stage_connection = || async {
+                self.begin_entities_transaction(establish_connection().await?)
+                    .await
+            };
+
+            let mut conn = Some(establish_stage_connection().await?);
+
+            // Create entities in stages as database-unrestricted user and get back connection if possible
+            for stage in 0..self.get_create_entities_stages() {
+                let stage_conn = match conn {
+                    None => establish_stage_connection().await?,
+                    Some(conn) => conn,
+                };
+                conn = self
+                    .create_entities(db_id, stage, stage_conn)
+                    .await
+                    .map_err(Into::into)?;
+
+                // Fail rather than resume on a fresh connection, the transaction having been rolled back
+                if conn.is_none() && self.get_create_entities_in_transaction() {
+                    return Err(BackendError::StageConnectionDropped(stage));
+                }
+            }
+
+            if let Some(conn) = &mut conn {
+                self.commit_entities_transaction(conn).await?;
+            }
+
+            // Create further entities over pool as database-unrestricted user
+            self.create_pool_entities(db_id, false).await?;
+
+            // Normalize ownership of entities created under other roles
+            self.reassign_owned(db_id).await?;
+
+            // Collect statistics for the created entities if needed
+            if self.get_analyze_after_create() {
+                let mut conn = match conn {
+                    None => establish_connection().await?,
+                    Some(conn) => conn,
+                };
+
+                self.execute_statement(postgres::ANALYZE, &mut conn).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn try_create(
+        &'backend self,
+        db_id: Uuid,
+        restrict_privileges: bool,
+    ) -> Result<B::Pool, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        self.try_create_database(db_id, restrict_privileges).await?;
+
+        // Skip connecting to database never created in dry run
+        if self.get_dry_run() {
+            return self.create_connection_pool(db_id).await.map_err(Into::into);
+        }
+
+        // Create connection pool with attached role
+        let pool = self
+            .retry_connection(|| self.create_connection_pool(db_id))
             .await
-            .unwrap()
+            .map_err(Into::into)?;
+
+        // Verify that database is usable by attached role if needed
+        if let Some(verify_query) = self.get_verify_query() {
+            let mut conn = self.establish_restricted_connection(db_id).await?;
+            self.execute_statement(verify_query, &mut conn).await?;
+        }
+
+        // Establish minimum idle connections before handing out pool if needed
+        if self.get_warm_up_pools()
+            && let Some(min_idle) = self.get_restricted_min_idle()
+        {
+            self.warm_up_pool(&pool, min_idle)
+                .await
+                .map_err(Into::into)?;
+        }
+
+        Ok(pool)
     }
 
-    async fn count_all_databases(conn: &mut AsyncPgConnection) -> i64 {
-        pg_database::table
-            .filter(pg_database::datname.like("db_pool_%"))
-            .count()
-            .get_result(conn)
+    async fn try_create_connection(
+        &'backend self,
+        db_id: Uuid,
+        restrict_privileges: bool,
+    ) -> Result<
+        B::Connection,
+        BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        self.try_create_database(db_id, restrict_privileges).await?;
+
+        // Fail since no connection can be established to database never created in dry run
+        if self.get_dry_run() {
+            return Err(BackendError::Unsupported(Operation::Create(
+                self.get_db_name(db_id),
+            )));
+        }
+
+        // Connect to database as attached role instead of creating connection pool
+        let mut conn = self.establish_restricted_connection(db_id).await?;
+
+        // Verify that database is usable by attached role if needed
+        if let Some(verify_query) = self.get_verify_query() {
+            self.execute_statement(verify_query, &mut conn).await?;
+        }
+
+        Ok(conn)
+    }
+
+    pub(super) async fn rebuild_pool(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<B::Pool, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Create new connection pool with attached role
+        self.create_connection_pool(db_id).await.map_err(Into::into)
+    }
+
+    pub(super) async fn database_exists(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<bool, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get database name based on UUID
+        let db_name = self.get_db_name(db_id);
+
+        // Get connection to default database as privileged user
+        let conn = &mut self
+            .acquire_default_connection(Operation::CheckExists(db_name.clone()))
+            .await?;
+
+        // Look database up among existing databases
+        let db_names = self
+            .get_previous_database_names(conn)
             .await
-            .unwrap()
+            .map_err(Into::into)?;
+        Ok(db_names.contains(&db_name))
     }
 
-    async fn database_exists(db_name: &str, conn: &mut AsyncPgConnection) -> bool {
-        select(exists(
-            pg_database::table.filter(pg_database::datname.eq(db_name)),
-        ))
-        .get_result(conn)
-        .await
-        .unwrap()
+    pub(super) async fn snapshot(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Skip snapshotting database never created in dry run
+        if self.get_dry_run() {
+            return Ok(());
+        }
+
+        let result = self.try_snapshot(db_id).await;
+
+        // Store new privileged connection to database for reuse when cleaning, also after failing
+        let stored = self.store_privileged_database_connection(db_id).await;
+        result.and(stored)
     }
 
-    async fn insert_books(count: i64, conn: &mut AsyncPgConnection) {
-        #[derive(Insertable)]
-        #[diesel(table_name = book)]
-        struct NewBook {
-            title: String,
+    async fn try_snapshot(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get database and snapshot names based on UUID
+        let db_name = self.get_db_name(db_id);
+        let db_name = db_name.as_str();
+        let snapshot_name = get_snapshot_db_name(db_name);
+        let snapshot_name = snapshot_name.as_str();
+
+        // Close stored privileged connection since a template must have no connections while being copied
+        drop(self.get_database_connection(db_id));
+
+        // Get connection to default database as privileged user
+        let conn = &mut self
+            .acquire_default_connection(Operation::Snapshot(db_name.to_owned()))
+            .await?;
+
+        // Terminate remaining connections to database and replace previous snapshot with its copy,
+        // running statements separately since databases cannot be created or dropped in a transaction
+        for stmt in [
+            postgres::terminate_database_connections(db_name),
+            postgres::drop_database_if_exists(snapshot_name),
+            postgres::create_database_from_template(snapshot_name, db_name, None, None),
+        ] {
+            self.execute_statement(stmt.as_str(), conn).await?;
         }
+        self.record_creation_time(snapshot_name, conn).await
+    }
 
-        let new_books = (0..count)
-            .map(|i| NewBook {
-                title: format!("Title {}", i + 1),
-            })
-            .collect::<Vec<_>>();
+    pub(super) async fn restore(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Skip restoring database never created in dry run
+        if self.get_dry_run() {
+            return Ok(());
+        }
 
-        insert_into(book::table)
-            .values(&new_books)
-            .execute(conn)
+        match self.try_restore(db_id).await {
+            Ok(db_conn) => {
+                // Store privileged connection to database for reuse when cleaning
+                self.put_database_connection(db_id, db_conn);
+                Ok(())
+            }
+            Err(err) => {
+                // Store new privileged connection to database if it still exists
+                self.store_privileged_database_connection(db_id).await.ok();
+                Err(err)
+            }
+        }
+    }
+
+    async fn try_restore(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<
+        B::Connection,
+        BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        // Get database and snapshot names based on UUID
+        let db_name = self.get_db_name(db_id);
+        let db_name = db_name.as_str();
+        let snapshot_name = get_snapshot_db_name(db_name);
+        let snapshot_name = snapshot_name.as_str();
+
+        // Close stored privileged connection to database
+        drop(self.get_database_connection(db_id));
+
+        // Get connection to default database as privileged user
+        let conn = &mut self
+            .acquire_default_connection(Operation::Restore(db_name.to_owned()))
+            .await?;
+
+        // Recreate database from snapshot
+        self.execute_statement(postgres::force_drop_database(db_name).as_str(), conn)
+            .await?;
+        self.execute_statement(
+            postgres::create_database_from_template(db_name, snapshot_name, None, None).as_str(),
+            conn,
+        )
+        .await?;
+        self.record_creation_time(db_name, conn).await?;
+
+        // Reapply database-level configuration not copied from snapshot
+        if let Some(owner) = self.get_database_owner() {
+            self.execute_statement(
+                postgres::grant_database_ownership(db_name, owner).as_str(),
+                conn,
+            )
+            .await?;
+        }
+        self.set_database_parameters(db_name, conn).await?;
+
+        let mut db_conn = self
+            .retry_connection(|| self.establish_privileged_database_connection(db_id))
             .await
-            .unwrap();
+            .map_err(Into::into)?;
+        let role_name = self.get_role_name(db_id);
+        for stmt in self.get_restricted_privileges().grant_statements(
+            db_name,
+            role_name.as_str(),
+            self.get_clean_schemas(),
+        ) {
+            self.execute_statement(stmt.as_str(), &mut db_conn).await?;
+        }
+
+        Ok(db_conn)
+    }
+
+    async fn store_privileged_database_connection(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        let db_conn = self
+            .retry_connection(|| self.establish_privileged_database_connection(db_id))
+            .await
+            .map_err(Into::into)?;
+        self.put_database_connection(db_id, db_conn);
+        Ok(())
+    }
+
+    pub(super) async fn clean(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        let start = Instant::now();
+        let result = self.try_clean(db_id).await;
+        self.observe(Phase::Clean, db_id, start, &result);
+        result
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        allow(clippy::complexity),
+        tracing::instrument(
+            name = "db_pool.clean",
+            skip_all,
+            fields(%db_id, db_name = %self.get_db_name(db_id))
+        )
+    )]
+    async fn try_clean(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Skip cleaning database never created in dry run
+        if self.get_dry_run() {
+            return Ok(());
+        }
+
+        // Get privileged connection to database
+        let mut conn = self.get_database_connection(db_id);
+
+        let result = self.clean_database(&mut conn).await;
+
+        // Store database connection back for reuse, even if cleaning failed
+        self.put_database_connection(db_id, conn);
+
+        result
+    }
+
+    async fn clean_database(
+        &'backend self,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get table names in cleaned schemas
+        let table_names = self.get_cleaned_table_names(conn).await?;
+
+        // Skip excluded tables
+        let excluded_table_names = self.get_excluded_tables();
+        let table_names = table_names
+            .iter()
+            .filter(|(schema_name, table_name)| {
+                !excluded_table_names
+                    .iter()
+                    .any(|name| matches_table_name(name, schema_name, table_name))
+            })
+            .map(|(schema_name, table_name)| (schema_name.as_str(), table_name.as_str()))
+            .collect::<Vec<_>>();
+
+        // Clean tables with custom statements if given, truncating remaining tables
+        let mut truncated_table_names = Vec::with_capacity(table_names.len());
+        for (schema_name, table_name) in table_names {
+            match self.get_clean_table_statements(table_name) {
+                Some(stmts) => {
+                    self.batch_execute_statements(stmts.into_iter().map(Cow::Owned), conn)
+                        .await?;
+                }
+                None => truncated_table_names.push((schema_name, table_name)),
+            }
+        }
+        let table_names = truncated_table_names;
+
+        // Truncate tables in batches, all at once if batching is disabled
+        let batch_size = match self.get_clean_batch_size() {
+            0 => table_names.len().max(1),
+            batch_size => batch_size,
+        };
+        for table_names in table_names.chunks(batch_size) {
+            let stmts = self
+                .get_truncate_mode()
+                .statements(table_names, self.get_fk_handling());
+            self.batch_execute_statements(stmts, conn).await?;
+        }
+
+        // Run maintenance standalone, as ``VACUUM`` cannot run within a transaction block
+        if let Some(stmt) = self.get_maintenance_mode().statement() {
+            self.execute_statement(stmt, conn).await?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) async fn clean_tables(
+        &'backend self,
+        db_id: Uuid,
+        table_names: &[&str],
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Skip cleaning database never created in dry run
+        if self.get_dry_run() {
+            return Ok(());
+        }
+
+        // Get privileged connection to database
+        let mut conn = self.get_database_connection(db_id);
+
+        let result = self.truncate_tables(table_names, &mut conn).await;
+
+        // Store database connection back for reuse, even if cleaning failed
+        self.put_database_connection(db_id, conn);
+
+        result
+    }
+
+    async fn truncate_tables(
+        &'backend self,
+        table_names: &[&str],
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get existing table names in cleaned schemas
+        let existing_table_names = self.get_cleaned_table_names(conn).await?;
+
+        // Qualify given tables with their schema
+        let mut qualified_table_names = Vec::new();
+        for table_name in table_names {
+            let len = qualified_table_names.len();
+            qualified_table_names.extend(
+                existing_table_names
+                    .iter()
+                    .filter(|(schema_name, name)| matches_table_name(table_name, schema_name, name))
+                    .map(|(schema_name, name)| (schema_name.as_str(), name.as_str())),
+            );
+            if qualified_table_names.len() == len {
+                return Err(BackendError::UnknownTable((*table_name).to_owned()));
+            }
+        }
+
+        // Generate truncate statements
+        let stmts = qualified_table_names
+            .iter()
+            .map(|(schema_name, table_name)| {
+                postgres::truncate_table(schema_name, table_name).into()
+            });
+
+        // Truncate tables
+        self.batch_execute_statements(stmts, conn).await
+    }
+
+    pub(super) async fn reset_sequences(
+        &'backend self,
+        db_id: Uuid,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Skip cleaning database never created in dry run
+        if self.get_dry_run() {
+            return Ok(());
+        }
+
+        // Get privileged connection to database
+        let mut conn = self.get_database_connection(db_id);
+
+        let result = self.restart_sequences(&mut conn).await;
+
+        // Store database connection back for reuse, even if restarting sequences failed
+        self.put_database_connection(db_id, conn);
+
+        result
+    }
+
+    async fn restart_sequences(
+        &'backend self,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get sequence names
+        let sequence_names = self.get_sequence_names(conn).await.map_err(Into::into)?;
+
+        // Generate restart statements
+        let stmts = sequence_names.iter().map(|(schema_name, sequence_name)| {
+            postgres::restart_sequence(schema_name, sequence_name).into()
+        });
+
+        // Restart sequences
+        self.batch_execute_statements(stmts, conn).await
+    }
+
+    pub(super) async fn drop(
+        &'backend self,
+        db_id: Uuid,
+        is_restricted: bool,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        let start = Instant::now();
+        let result = self.try_drop(db_id, is_restricted).await;
+        self.observe(Phase::Drop, db_id, start, &result);
+        result
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        allow(clippy::complexity),
+        tracing::instrument(
+            name = "db_pool.drop",
+            skip_all,
+            fields(%db_id, db_name = %self.get_db_name(db_id), is_restricted)
+        )
+    )]
+    async fn try_drop(
+        &'backend self,
+        db_id: Uuid,
+        is_restricted: bool,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Get privileged connection to database if stored, none being stored in dry run
+        let dry_run = self.get_dry_run();
+        let conn = (is_restricted && !dry_run).then(|| self.get_database_connection(db_id));
+
+        // Run hook with privileged connection to database if needed, dropping connection afterwards
+        if self.has_before_drop() && !dry_run {
+            let conn = match conn {
+                None => self
+                    .establish_privileged_database_connection(db_id)
+                    .await
+                    .map_err(Into::into)?,
+                Some(conn) => conn,
+            };
+            self.before_drop(db_id, conn).await;
+        } else {
+            drop(conn);
+        }
+
+        // Get database name based on UUID
+        let db_name = self.get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        // Get connection to default database as privileged user
+        let conn = &mut self
+            .acquire_default_connection(Operation::Drop(db_name.to_owned()))
+            .await?;
+
+        // Terminate lingering connections to database if needed
+        if self.get_terminate_connections_before_drop() {
+            self.execute_statement(
+                postgres::terminate_database_connections(db_name).as_str(),
+                conn,
+            )
+            .await?;
+        }
+
+        // Fail fast instead of waiting on locks held on database if needed
+        let lock_timeout = self.get_drop_lock_timeout();
+        if let Some(lock_timeout) = lock_timeout {
+            let lock_timeout = format!("{}ms", lock_timeout.as_millis());
+            self.execute_statement(
+                postgres::set_lock_timeout(lock_timeout.as_str()).as_str(),
+                conn,
+            )
+            .await?;
+        }
+
+        let result = self
+            .drop_database_and_role(db_name, is_restricted, conn)
+            .await;
+
+        // Restore lock timeout before returning connection to pool
+        if lock_timeout.is_some() {
+            let reset = self
+                .execute_statement(postgres::RESET_LOCK_TIMEOUT, conn)
+                .await;
+            result.and(reset)?;
+        } else {
+            result?;
+        }
+
+        // Release generated name
+        if let Some(generator) = self.get_db_name_generator() {
+            generator.forget(db_id);
+        }
+
+        Ok(())
+    }
+
+    async fn drop_database_and_role(
+        &'backend self,
+        db_name: &str,
+        is_restricted: bool,
+        conn: &mut B::Connection,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Drop database, retrying within grace period and forcing afterwards if needed
+        if let Some(grace_period) = self.get_drop_grace_period() {
+            let deadline = Instant::now() + grace_period;
+            loop {
+                match self
+                    .execute_query(postgres::drop_database(db_name).as_str(), conn)
+                    .await
+                {
+                    Ok(()) => break,
+                    Err(_) if Instant::now() < deadline => sleep(DROP_POLL_INTERVAL).await,
+                    Err(_) => {
+                        self.execute_statement(
+                            postgres::force_drop_database(db_name).as_str(),
+                            conn,
+                        )
+                        .await?;
+                        break;
+                    }
+                }
+            }
+        } else {
+            self.execute_statement(postgres::drop_database(db_name).as_str(), conn)
+                .await?;
+        }
+
+        // Drop snapshot if taken
+        if is_restricted {
+            self.execute_statement(
+                postgres::drop_database_if_exists(get_snapshot_db_name(db_name).as_str()).as_str(),
+                conn,
+            )
+            .await?;
+        }
+
+        // Drop attached role unless shared across databases
+        if self.get_shared_role().is_none() {
+            self.execute_statement(postgres::drop_role(db_name).as_str(), conn)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+// Match table by bare name or by name qualified with its schema
+pub(super) fn matches_table_name(name: &str, schema_name: &str, table_name: &str) -> bool {
+    name == table_name || name.split_once('.') == Some((schema_name, table_name))
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{
+        fmt::Debug,
+        panic::AssertUnwindSafe,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use bb8::Pool as Bb8Pool;
+    use diesel::{delete, dsl::exists, insert_into, prelude::*, select, sql_query, table};
+    use diesel_async::{
+        AsyncConnection, AsyncPgConnection, RunQueryDsl, SimpleAsyncConnection,
+        pooled_connection::AsyncDieselConnectionManager,
+    };
+    use futures::{
+        Future, FutureExt,
+        future::{join_all, try_join_all},
+    };
+    use parking_lot::Mutex;
+    use tokio::{
+        sync::OnceCell,
+        time::{Instant, sleep, timeout},
+    };
+    use uuid::Uuid;
+
+    use super::{Observer, Phase, RoleAttributes, matches_table_name};
+    use crate::{
+        r#async::{
+            backend::{Error as BackendError, r#trait::Backend},
+            db_pool::{DatabasePoolBuilder, PoolStats},
+        },
+        common::{
+            config::postgres::RestrictedConnectionParams,
+            statement::postgres::{
+                self,
+                tests::{DDL_STATEMENTS, DML_STATEMENTS},
+            },
+        },
+        tests::{PG_DROP_LOCK, get_privileged_postgres_config},
+        util::{DEFAULT_DB_NAME_PREFIX, get_db_name, get_prefixed_db_name},
+    };
+
+    pub type Pool = Bb8Pool<AsyncDieselConnectionManager<AsyncPgConnection>>;
+
+    table! {
+        pg_database (oid) {
+            oid -> Int4,
+            datname -> Text
+        }
+    }
+
+    table! {
+        pg_roles (oid) {
+            oid -> Int4,
+            rolname -> Text
+        }
+    }
+
+    table! {
+        book (id) {
+            id -> Int4,
+            title -> Text
+        }
+    }
+
+    table! {
+        pg_tables (tablename) {
+            tablename -> Text,
+            tableowner -> Text
+        }
+    }
+
+    table! {
+        dummy (id) {
+            id -> Int4
+        }
+    }
+
+    table! {
+        pg_class (oid) {
+            oid -> Int4,
+            relname -> Text,
+            reltuples -> Float4
+        }
+    }
+
+    #[derive(Clone, Default)]
+    pub struct CountingObserver {
+        creates: Arc<AtomicUsize>,
+        cleans: Arc<AtomicUsize>,
+        drops: Arc<AtomicUsize>,
+        errors: Arc<AtomicUsize>,
+    }
+
+    impl Observer for CountingObserver {
+        fn on_create(&self, _: Uuid, _: Duration) {
+            self.creates.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_clean(&self, _: Uuid, _: Duration) {
+            self.cleans.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_drop(&self, _: Uuid, _: Duration) {
+            self.drops.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_error(&self, _: Phase, _: &dyn Debug) {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub trait PgDropLock<T>
+    where
+        Self: Future<Output = T> + Sized,
+    {
+        async fn lock_drop(self) -> T {
+            let guard = PG_DROP_LOCK.write().await;
+            self.await
+        }
+
+        async fn lock_read(self) -> T {
+            let guard = PG_DROP_LOCK.read().await;
+            self.await
+        }
+    }
+
+    impl<T, F> PgDropLock<T> for F where F: Future<Output = T> + Sized {}
+
+    pub async fn get_privileged_connection_pool() -> &'static Pool {
+        static POOL: OnceCell<Pool> = OnceCell::const_new();
+        POOL.get_or_init(|| async {
+            let config = get_privileged_postgres_config();
+            let connection_url = config.default_connection_url();
+            let manager = AsyncDieselConnectionManager::new(connection_url);
+            Bb8Pool::builder().build(manager).await.unwrap()
+        })
+        .await
+    }
+
+    async fn create_restricted_connection_pool(db_name: &str) -> Pool {
+        let config = get_privileged_postgres_config();
+        let connection_url =
+            config.restricted_connection_url(&RestrictedConnectionParams::for_database(db_name));
+        let manager = AsyncDieselConnectionManager::new(connection_url);
+        Bb8Pool::builder().build(manager).await.unwrap()
+    }
+
+    async fn create_database(prefix: &str, conn: &mut AsyncPgConnection) -> String {
+        let db_id = Uuid::new_v4();
+        let db_name = get_prefixed_db_name(prefix, db_id);
+        sql_query(format!("CREATE DATABASE {db_name}"))
+            .execute(conn)
+            .await
+            .unwrap();
+        db_name
+    }
+
+    async fn create_databases(prefix: &str, count: i64, pool: &Pool) -> Vec<String> {
+        let futures = (0..count)
+            .map(|_| async {
+                let conn = &mut pool.get().await.unwrap();
+                create_database(prefix, conn).await
+            })
+            .collect::<Vec<_>>();
+        join_all(futures).await
+    }
+
+    async fn count_databases(db_names: &Vec<String>, conn: &mut AsyncPgConnection) -> i64 {
+        pg_database::table
+            .filter(pg_database::datname.eq_any(db_names))
+            .count()
+            .get_result(conn)
+            .await
+            .unwrap()
+    }
+
+    async fn count_all_databases(conn: &mut AsyncPgConnection) -> i64 {
+        pg_database::table
+            .filter(pg_database::datname.like("db_pool_%"))
+            .count()
+            .get_result(conn)
+            .await
+            .unwrap()
+    }
+
+    pub async fn database_exists(db_name: &str, conn: &mut AsyncPgConnection) -> bool {
+        select(exists(
+            pg_database::table.filter(pg_database::datname.eq(db_name)),
+        ))
+        .get_result(conn)
+        .await
+        .unwrap()
+    }
+
+    pub async fn role_exists(role_name: &str, conn: &mut AsyncPgConnection) -> bool {
+        select(exists(
+            pg_roles::table.filter(pg_roles::rolname.eq(role_name)),
+        ))
+        .get_result(conn)
+        .await
+        .unwrap()
+    }
+
+    async fn get_database_owner(db_name: &str, conn: &mut AsyncPgConnection) -> String {
+        #[derive(QueryableByName)]
+        struct DatabaseOwner {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            owner: String,
+        }
+
+        sql_query("SELECT pg_get_userbyid(datdba) AS owner FROM pg_database WHERE datname = $1")
+            .bind::<diesel::sql_types::Text, _>(db_name)
+            .get_result::<DatabaseOwner>(conn)
+            .await
+            .unwrap()
+            .owner
+    }
+
+    pub async fn create_owner_role() -> String {
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        let owner = format!("owner_{}", Uuid::new_v4().simple());
+        sql_query(postgres::create_role(owner.as_str(), owner.as_str(), None))
+            .execute(conn)
+            .await
+            .unwrap();
+        owner
+    }
+
+    async fn insert_books(count: i64, conn: &mut AsyncPgConnection) {
+        #[derive(Insertable)]
+        #[diesel(table_name = book)]
+        struct NewBook {
+            title: String,
+        }
+
+        let new_books = (0..count)
+            .map(|i| NewBook {
+                title: format!("Title {}", i + 1),
+            })
+            .collect::<Vec<_>>();
+
+        insert_into(book::table)
+            .values(&new_books)
+            .execute(conn)
+            .await
+            .unwrap();
+    }
+
+    pub async fn test_backend_creates_databases_concurrently(backend: impl Backend) {
+        const NUM_DBS: usize = 20;
+
+        async {
+            let db_ids = (0..NUM_DBS).map(|_| Uuid::new_v4()).collect::<Vec<_>>();
+
+            // all creations must succeed
+            let results = join_all(db_ids.iter().map(|db_id| backend.create(*db_id, true))).await;
+            assert!(results.iter().all(Result::is_ok));
+            drop(results);
+
+            for db_id in db_ids {
+                backend.drop(db_id, true).await.unwrap();
+            }
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_drops_previous_databases<B: Backend>(
+        default: B,
+        enabled: B,
+        disabled: B,
+    ) {
+        const NUM_DBS: i64 = 3;
+
+        async {
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            for (backend, cleans) in [(default, true), (enabled, true), (disabled, false)] {
+                let db_names = create_databases(DEFAULT_DB_NAME_PREFIX, NUM_DBS, conn_pool).await;
+                assert_eq!(count_databases(&db_names, conn).await, NUM_DBS);
+                backend.init().await.unwrap();
+                assert_eq!(
+                    count_databases(&db_names, conn).await,
+                    if cleans { 0 } else { NUM_DBS }
+                );
+            }
+        }
+        .lock_drop()
+        .await;
+    }
+
+    pub async fn test_backend_warns_about_previous_databases(backend: impl Backend) -> Vec<String> {
+        const NUM_DBS: i64 = 3;
+
+        async {
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // previous databases must survive initialization
+            let db_names = create_databases(DEFAULT_DB_NAME_PREFIX, NUM_DBS, conn_pool).await;
+            backend.init().await.unwrap();
+            assert_eq!(count_databases(&db_names, conn).await, NUM_DBS);
+
+            for db_name in &db_names {
+                sql_query(postgres::force_drop_database(db_name))
+                    .execute(conn)
+                    .await
+                    .unwrap();
+            }
+
+            db_names
+        }
+        .lock_drop()
+        .await
+    }
+
+    pub async fn test_backend_drops_previous_databases_with_concurrency<B: Backend>(
+        serial: B,
+        bounded: B,
+    ) {
+        const NUM_DBS: i64 = 10;
+
+        async {
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            for backend in [serial, bounded] {
+                let db_names = create_databases(DEFAULT_DB_NAME_PREFIX, NUM_DBS, conn_pool).await;
+                assert_eq!(count_databases(&db_names, conn).await, NUM_DBS);
+                backend.init().await.unwrap();
+                assert_eq!(count_databases(&db_names, conn).await, 0);
+            }
+        }
+        .lock_drop()
+        .await;
+    }
+
+    pub async fn test_backend_drops_only_expired_previous_databases(backend: impl Backend) {
+        const NUM_DBS: i64 = 3;
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+
+        async {
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // create fresh database and databases of unknown age
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+            let db_names = create_databases(DEFAULT_DB_NAME_PREFIX, NUM_DBS, conn_pool).await;
+
+            // only databases of unknown age must be dropped
+            backend.init().await.unwrap();
+            assert!(database_exists(db_name.as_str(), conn).await);
+            assert_eq!(count_databases(&db_names, conn).await, 0);
+
+            backend.drop(db_id, true).await.unwrap();
+        }
+        .lock_drop()
+        .await;
+    }
+
+    pub async fn test_backend_drops_previous_databases_with_own_prefix<B: Backend>(
+        default: B,
+        prefixed: B,
+        prefix: &str,
+    ) {
+        const NUM_DBS: i64 = 3;
+
+        async {
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            let default_db_names =
+                create_databases(DEFAULT_DB_NAME_PREFIX, NUM_DBS, conn_pool).await;
+            let prefixed_db_names = create_databases(prefix, NUM_DBS, conn_pool).await;
+
+            // only databases with other prefix must be dropped
+            prefixed.init().await.unwrap();
+            assert_eq!(count_databases(&default_db_names, conn).await, NUM_DBS);
+            assert_eq!(count_databases(&prefixed_db_names, conn).await, 0);
+
+            // only databases with default prefix must be dropped
+            let prefixed_db_names = create_databases(prefix, NUM_DBS, conn_pool).await;
+            default.init().await.unwrap();
+            assert_eq!(count_databases(&default_db_names, conn).await, 0);
+            assert_eq!(count_databases(&prefixed_db_names, conn).await, NUM_DBS);
+
+            // clean up databases with other prefix
+            prefixed.init().await.unwrap();
+            assert_eq!(count_databases(&prefixed_db_names, conn).await, 0);
+        }
+        .lock_drop()
+        .await;
+    }
+
+    pub async fn test_backend_creates_database_with_restricted_privileges(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            // privileged operations
+            {
+                let conn_pool = get_privileged_connection_pool().await;
+                let conn = &mut conn_pool.get().await.unwrap();
+
+                // database must not exist
+                assert!(!database_exists(db_name, conn).await);
+
+                // database must exist after creating through backend
+                backend.init().await.unwrap();
+                backend.create(db_id, true).await.unwrap();
+                assert!(database_exists(db_name, conn).await);
+            }
+
+            // restricted operations
+            {
+                let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+                let conn = &mut conn_pool.get().await.unwrap();
+
+                // DDL statements must fail
+                for stmt in DDL_STATEMENTS {
+                    assert!(sql_query(stmt).execute(conn).await.is_err());
+                }
+
+                // DML statements must succeed
+                for stmt in DML_STATEMENTS {
+                    assert!(sql_query(stmt).execute(conn).await.is_ok());
+                }
+            }
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_reports_lifecycle_to_observer(
+        backend: impl Backend,
+        observer: CountingObserver,
+    ) {
+        async {
+            let db_id = Uuid::new_v4();
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+            backend.drop(db_id, true).await.unwrap();
+
+            // create and drop must have been reported once each
+            assert_eq!(AtomicUsize::load(&observer.creates, Ordering::Relaxed), 1);
+            assert_eq!(AtomicUsize::load(&observer.cleans, Ordering::Relaxed), 0);
+            assert_eq!(AtomicUsize::load(&observer.drops, Ordering::Relaxed), 1);
+            assert_eq!(AtomicUsize::load(&observer.errors, Ordering::Relaxed), 0);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub fn collide_db_names() -> impl Fn() -> String + Send + Sync + 'static {
+        let counter = AtomicUsize::new(0);
+        move || {
+            // First two names collide
+            format!(
+                "db_pool_race_{}",
+                counter.fetch_add(1, Ordering::Relaxed).max(1)
+            )
+        }
+    }
+
+    pub async fn test_backend_tolerates_concurrent_creates(backend: impl Backend) {
+        async {
+            let db_ids = [Uuid::new_v4(), Uuid::new_v4()];
+            backend.init().await.unwrap();
+
+            // Create databases with colliding names concurrently
+            let results = join_all(db_ids.map(|db_id| backend.create(db_id, true))).await;
+
+            // losing creation must be retried with a newly generated name
+            assert!(results.iter().all(Result::is_ok));
+            assert_ne!(
+                backend.get_database_name(db_ids[0]),
+                backend.get_database_name(db_ids[1])
+            );
+
+            for db_id in db_ids {
+                backend.drop(db_id, true).await.unwrap();
+            }
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_creates_read_only_database(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            {
+                let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+                let conn = &mut conn_pool.get().await.unwrap();
+
+                // reading must succeed
+                assert!(sql_query("SELECT * FROM book").execute(conn).await.is_ok());
+
+                // writing must fail
+                for stmt in DML_STATEMENTS.iter().skip(1) {
+                    assert!(sql_query(*stmt).execute(conn).await.is_err());
+                }
+            }
+
+            backend.drop(db_id, true).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub fn count_db_names() -> impl Fn() -> String + Send + Sync + 'static {
+        let counter = AtomicUsize::new(0);
+        move || {
+            format!(
+                "db_pool_test_{}",
+                counter.fetch_add(1, Ordering::Relaxed) + 1
+            )
+        }
+    }
+
+    pub async fn test_backend_creates_databases_with_generated_names(backend: impl Backend) {
+        async {
+            backend.init().await.unwrap();
+
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            let db_ids = [Uuid::new_v4(), Uuid::new_v4()];
+            for (i, db_id) in db_ids.into_iter().enumerate() {
+                backend.create(db_id, true).await.unwrap();
+
+                // database must have been created with generated name
+                let db_name = format!("db_pool_test_{}", i + 1);
+                assert_eq!(backend.get_database_name(db_id), db_name);
+                assert!(database_exists(db_name.as_str(), conn).await);
+            }
+
+            // databases with generated names must be dropped as previous databases
+            backend.init().await.unwrap();
+            for i in 1..=db_ids.len() {
+                let db_name = format!("db_pool_test_{i}");
+                assert!(!database_exists(db_name.as_str(), conn).await);
+            }
+        }
+        .lock_drop()
+        .await;
+    }
+
+    pub async fn test_backend_rejects_invalid_generated_name(backend: impl Backend) {
+        async {
+            backend.init().await.unwrap();
+
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // invalid name must be reported instead of panicking
+            let db_id = Uuid::new_v4();
+            assert!(matches!(
+                backend.create(db_id, true).await,
+                Err(BackendError::InvalidDbName(name)) if name == "other_test_1"
+            ));
+            assert!(!database_exists("other_test_1", conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_discards_database_when_creating_entities_panics(
+        backend: impl Backend,
+    ) {
+        async {
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            for restrict_privileges in [true, false] {
+                let db_id = Uuid::new_v4();
+                let db_name = get_db_name(db_id);
+                let db_name = db_name.as_str();
+
+                // panic must be propagated
+                let result = AssertUnwindSafe(backend.create(db_id, restrict_privileges))
+                    .catch_unwind()
+                    .await;
+                assert!(result.is_err());
+
+                // neither database nor role must survive
+                assert!(!database_exists(db_name, conn).await);
+                assert!(!role_exists(db_name, conn).await);
+            }
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_discards_database_when_creating_entities_fails(
+        backend: impl Backend,
+    ) {
+        async {
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            for restrict_privileges in [true, false] {
+                let db_id = Uuid::new_v4();
+                let db_name = get_db_name(db_id);
+                let db_name = db_name.as_str();
+
+                // error must be returned
+                assert!(backend.create(db_id, restrict_privileges).await.is_err());
+
+                // neither database nor role must survive
+                assert!(!database_exists(db_name, conn).await);
+                assert!(!role_exists(db_name, conn).await);
+            }
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_creates_database_with_configured_owner(
+        backend: impl Backend,
+        owner: &str,
+    ) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, false).await.unwrap();
+
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // database must be owned by configured owner role
+            assert_eq!(get_database_owner(db_name, conn).await, owner);
+
+            backend.drop(db_id, false).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_drops_database_with_lingering_connection(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            // hold connection to database while dropping it
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let _lingering_conn = conn_pool.get().await.unwrap();
+
+            backend.drop(db_id, true).await.unwrap();
+
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+            assert!(!database_exists(db_name, conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_sets_statement_timeout(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // long-running query must be canceled
+            let err = sql_query("SELECT pg_sleep(1)")
+                .execute(conn)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("statement timeout"));
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_grants_configured_restricted_privileges(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // granted statements must succeed
+            for stmt in [
+                "SELECT * FROM book",
+                "INSERT INTO book (title) VALUES ('Title')",
+            ] {
+                assert!(sql_query(stmt).execute(conn).await.is_ok());
+            }
+
+            // withheld statements must fail
+            for stmt in [
+                "UPDATE book SET title = 'Title 2' WHERE id = 1",
+                "DELETE FROM book WHERE id = 1",
+            ] {
+                assert!(sql_query(stmt).execute(conn).await.is_err());
+            }
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_creates_database_with_collation(
+        backend: impl Backend,
+        collation: &str,
+    ) {
+        table! {
+            pg_database (oid) {
+                oid -> Int4,
+                datname -> Text,
+                datcollate -> Text
+            }
+        }
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            // database must have been created with configured collation
+            assert_eq!(
+                pg_database::table
+                    .select(pg_database::datcollate)
+                    .filter(pg_database::datname.eq(db_name))
+                    .first::<String>(conn)
+                    .await
+                    .unwrap(),
+                collation
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_creates_database_with_unrestricted_privileges(backend: impl Backend) {
+        async {
+            {
+                let db_id = Uuid::new_v4();
+                let db_name = get_db_name(db_id);
+                let db_name = db_name.as_str();
+
+                // privileged operations
+                {
+                    let conn_pool = get_privileged_connection_pool().await;
+                    let conn = &mut conn_pool.get().await.unwrap();
+
+                    // database must not exist
+                    assert!(!database_exists(db_name, conn).await);
+
+                    // database must exist after creating through backend
+                    backend.init().await.unwrap();
+                    backend.create(db_id, false).await.unwrap();
+                    assert!(database_exists(db_name, conn).await);
+                }
+
+                // DML statements must succeed
+                {
+                    let conn_pool = create_restricted_connection_pool(db_name).await;
+                    let conn = &mut conn_pool.get().await.unwrap();
+                    for stmt in DML_STATEMENTS {
+                        let result = sql_query(stmt).execute(conn).await;
+                        assert!(result.is_ok());
+                    }
+                }
+            }
+
+            // DDL statements must succeed
+            try_join_all(DDL_STATEMENTS.iter().map(|stmt| {
+                let backend = &backend;
+                async move {
+                    let db_id = Uuid::new_v4();
+                    let db_name = get_db_name(db_id);
+                    let db_name = db_name.as_str();
+
+                    backend.create(db_id, false).await.unwrap();
+                    let conn_pool = create_restricted_connection_pool(db_name).await;
+                    let conn = &mut conn_pool.get().await.unwrap();
+
+                    sql_query(*stmt).execute(conn).await
+                }
+            }))
+            .await
+            .unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_creates_extensions(backend: impl Backend) {
+        table! {
+            pg_extension (oid) {
+                oid -> Int4,
+                extname -> Text
+            }
+        }
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let conn_pool = create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // extension must have been created
+            assert!(
+                select(exists(
+                    pg_extension::table.filter(pg_extension::extname.eq("pgcrypto"))
+                ))
+                .get_result::<bool>(conn)
+                .await
+                .unwrap()
+            );
+
+            // extension functions must be usable by restricted role
+            sql_query("SELECT gen_random_uuid(), gen_random_bytes(16)")
+                .execute(conn)
+                .await
+                .unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_verifies_privileges(backend: impl Backend) {
+        // privileged role used for tests is a superuser
+        backend.verify().await.unwrap();
+    }
+
+    pub async fn test_backend_skips_statements_in_dry_run(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            // database must not have been created
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+            assert!(!database_exists(db_name, conn).await);
+
+            // cleaning and dropping must not touch server
+            backend.clean(db_id).await.unwrap();
+            backend.drop(db_id, true).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_limits_role_connections(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let config = get_privileged_postgres_config();
+            let connection_url = config
+                .restricted_connection_url(&RestrictedConnectionParams::for_database(db_name));
+
+            // first connection must be accepted
+            let _conn = AsyncPgConnection::establish(connection_url.as_str())
+                .await
+                .unwrap();
+
+            // second connection must be rejected
+            assert!(
+                AsyncPgConnection::establish(connection_url.as_str())
+                    .await
+                    .is_err()
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_shares_role(backend: impl Backend, role_name: &str) {
+        let db_ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let db_names = db_ids.map(get_db_name);
+
+        async {
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            backend.init().await.unwrap();
+            for db_id in db_ids {
+                backend.create(db_id, true).await.unwrap();
+            }
+
+            let config = get_privileged_postgres_config();
+            for db_name in &db_names {
+                // no role must be attached to database
+                assert!(!role_exists(db_name, conn).await);
+
+                // shared role must be able to access database
+                let connection_url =
+                    config.restricted_connection_url(&RestrictedConnectionParams {
+                        database: db_name,
+                        role: role_name,
+                        password: Some(role_name),
+                        search_path: None,
+                    });
+                let db_conn = &mut AsyncPgConnection::establish(connection_url.as_str())
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    book::table
+                        .count()
+                        .get_result::<i64>(db_conn)
+                        .await
+                        .unwrap(),
+                    0
+                );
+            }
+
+            // shared role must survive dropping databases
+            for db_id in db_ids {
+                backend.drop(db_id, true).await.unwrap();
+                assert!(role_exists(role_name, conn).await);
+            }
+
+            sql_query(postgres::drop_role(role_name))
+                .execute(conn)
+                .await
+                .unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_uses_restricted_password(backend: impl Backend, password: &str) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let config = get_privileged_postgres_config();
+            let connection_url = |password| {
+                config.restricted_connection_url(&RestrictedConnectionParams {
+                    password: Some(password),
+                    ..RestrictedConnectionParams::for_database(db_name)
+                })
+            };
+
+            // restricted role must connect with configured password
+            AsyncPgConnection::establish(connection_url(password).as_str())
+                .await
+                .unwrap();
+
+            // restricted role must not connect with database name as password
+            assert!(
+                AsyncPgConnection::establish(connection_url(db_name).as_str())
+                    .await
+                    .is_err()
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_reports_failed_statement(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+
+            // create role left behind by a previous run
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+            sql_query(postgres::create_role(db_name, db_name, None))
+                .execute(conn)
+                .await
+                .unwrap();
+
+            // error must carry statement creating duplicate role
+            let Err(BackendError::Statement(stmt, _)) = backend.create(db_id, true).await else {
+                panic!("creating duplicate role must fail with statement");
+            };
+            assert!(stmt.starts_with(format!("CREATE ROLE {db_name}").as_str()));
+
+            // neither database nor role must survive
+            assert!(!database_exists(db_name, conn).await);
+            assert!(!role_exists(db_name, conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_creates_entities_in_stages(backend: impl Backend, restricted: bool) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, restricted).await.unwrap();
+
+            let conn_pool = create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // book must have been inserted after table was created
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_rolls_back_entities_in_transaction(
+        backend: impl Backend,
+        restricted: bool,
+    ) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+
+            // creating entities must fail midway
+            assert!(backend.create(db_id, restricted).await.is_err());
+
+            // database must have been discarded along with rolled back entities
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+            assert!(!database_exists(db_name, conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_fails_on_stage_dropping_transaction(
+        backend: impl Backend,
+        restricted: bool,
+    ) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+
+            // stage dropping its connection must fail creation
+            assert!(matches!(
+                backend.create(db_id, restricted).await,
+                Err(BackendError::StageConnectionDropped(0))
+            ));
+
+            // database must have been discarded
+            let conn_pool = get_privileged_connection_pool().await;
+            let conn = &mut conn_pool.get().await.unwrap();
+            assert!(!database_exists(db_name, conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_creates_entities_with_pool(backend: impl Backend, restricted: bool) {
+        table! {
+            author (id) {
+                id -> Int4,
+                name -> Text
+            }
+        }
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, restricted).await.unwrap();
+
+            let conn_pool = create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // both tables must have been seeded over pool
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
+            assert_eq!(
+                author::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_passes_context_to_entity_creation(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, false).await.unwrap();
+
+            let conn_pool = create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // book must have been titled after the created database
+            assert_eq!(
+                book::table
+                    .select(book::title)
+                    .load::<String>(conn)
+                    .await
+                    .unwrap(),
+                vec![db_name.to_owned()]
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_clones_entity_template(
+        backend: impl Backend,
+        entity_creations: Arc<AtomicUsize>,
+    ) {
+        const NUM_DBS: usize = 2;
+
+        let db_ids = (0..NUM_DBS).map(|_| Uuid::new_v4()).collect::<Vec<_>>();
+
+        async {
+            backend.init().await.unwrap();
+
+            // entities must have been created once upon initialization
+            assert_eq!(AtomicUsize::load(&entity_creations, Ordering::Relaxed), 1);
+
+            for db_id in &db_ids {
+                backend.create(*db_id, true).await.unwrap();
+
+                let db_name = get_db_name(*db_id);
+                let conn_pool = create_restricted_connection_pool(db_name.as_str()).await;
+                let conn = &mut conn_pool.get().await.unwrap();
+
+                // table must have been cloned from template
+                assert_eq!(
+                    book::table.count().get_result::<i64>(conn).await.unwrap(),
+                    0
+                );
+            }
+
+            // entities must not have been created again for each database
+            assert_eq!(AtomicUsize::load(&entity_creations, Ordering::Relaxed), 1);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_runs_verify_query(valid: impl Backend, invalid: impl Backend) {
+        async {
+            valid.init().await.unwrap();
+            invalid.init().await.unwrap();
+
+            // valid verify query must pass
+            valid.create(Uuid::new_v4(), true).await.unwrap();
+
+            // invalid verify query must fail creation
+            assert!(matches!(
+                invalid.create(Uuid::new_v4(), true).await,
+                Err(BackendError::Statement(_, _))
+            ));
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_cleans_database_with_tables(backend: impl Backend) {
+        const NUM_BOOKS: i64 = 3;
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            insert_books(NUM_BOOKS, conn).await;
+
+            // there must be books
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                NUM_BOOKS
+            );
+
+            backend.clean(db_id).await.unwrap();
+
+            // there must be no books
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_cleans_table_with_custom_statements(backend: impl Backend) {
+        const NUM_BOOKS: i64 = 3;
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            insert_books(NUM_BOOKS, conn).await;
+
+            backend.clean(db_id).await.unwrap();
+
+            // only seed book must be left
+            assert_eq!(
+                book::table
+                    .select(book::title)
+                    .load::<String>(conn)
+                    .await
+                    .unwrap(),
+                vec!["Title 1"]
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub fn fail_first_clean() -> impl Fn(&str) -> Option<Vec<String>> + Send + Sync + 'static {
+        let counter = AtomicUsize::new(0);
+        move |table_name| {
+            // First attempt to clean book fails
+            (table_name == "book" && counter.fetch_add(1, Ordering::Relaxed) == 0)
+                .then(|| vec!["DELETE FROM missing".to_owned()])
+        }
+    }
+
+    pub async fn test_backend_retries_clean_after_failed_attempt(backend: impl Backend) {
+        const NUM_BOOKS: i64 = 3;
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            {
+                let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+                let conn = &mut conn_pool.get().await.unwrap();
+
+                insert_books(NUM_BOOKS, conn).await;
+
+                // first attempt must fail
+                assert!(backend.clean(db_id).await.is_err());
+
+                // retry must reuse stored connection
+                backend.clean(db_id).await.unwrap();
+
+                assert_eq!(
+                    book::table.count().get_result::<i64>(conn).await.unwrap(),
+                    0
+                );
+            }
+
+            backend.drop(db_id, true).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_grants_restricted_privileges_in_schemas(backend: impl Backend) {
+        table! {
+            billing.invoice (id) {
+                id -> Int4
+            }
+        }
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            {
+                let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+                let conn = &mut conn_pool.get().await.unwrap();
+
+                // restricted role must access tables outside of public schema
+                insert_into(invoice::table)
+                    .default_values()
+                    .execute(conn)
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    invoice::table
+                        .count()
+                        .get_result::<i64>(conn)
+                        .await
+                        .unwrap(),
+                    1
+                );
+            }
+
+            backend.drop(db_id, true).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_cleans_database_in_schemas(backend: impl Backend) {
+        table! {
+            billing.invoice (id) {
+                id -> Int4
+            }
+        }
+
+        table! {
+            audit.entry (id) {
+                id -> Int4
+            }
+        }
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            // Create tables outside of public schema
+            let config = get_privileged_postgres_config();
+            let conn = &mut AsyncPgConnection::establish(
+                config.privileged_database_connection_url(db_name).as_str(),
+            )
+            .await
+            .unwrap();
+            conn.batch_execute(
+                "CREATE SCHEMA billing; CREATE TABLE billing.invoice(id SERIAL PRIMARY KEY); INSERT INTO billing.invoice DEFAULT VALUES; \
+                 CREATE SCHEMA audit; CREATE TABLE audit.entry(id SERIAL PRIMARY KEY); INSERT INTO audit.entry DEFAULT VALUES",
+            )
+            .await
+            .unwrap();
+            insert_books(1, conn).await;
+
+            backend.clean(db_id).await.unwrap();
+
+            // tables in cleaned schemas must be empty
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
+            assert_eq!(
+                invoice::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
+
+            // tables in other schemas must be left untouched
+            assert_eq!(
+                entry::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_cleans_partitioned_table(backend: impl Backend) {
+        table! {
+            reading (id) {
+                id -> Int4
+            }
+        }
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            // Create range-partitioned table with rows in each partition
+            let config = get_privileged_postgres_config();
+            let conn = &mut AsyncPgConnection::establish(
+                config.privileged_database_connection_url(db_name).as_str(),
+            )
+            .await
+            .unwrap();
+            conn.batch_execute(
+                "CREATE TABLE reading(id INT NOT NULL, taken_on DATE NOT NULL) PARTITION BY RANGE (taken_on); \
+                 CREATE TABLE reading_2025 PARTITION OF reading FOR VALUES FROM ('2025-01-01') TO ('2026-01-01'); \
+                 CREATE TABLE reading_2026 PARTITION OF reading FOR VALUES FROM ('2026-01-01') TO ('2027-01-01'); \
+                 INSERT INTO reading VALUES (1, '2025-06-01'), (2, '2026-06-01')",
+            )
+            .await
+            .unwrap();
+
+            backend.clean(db_id).await.unwrap();
+
+            // partitioned table must be empty
+            assert_eq!(
+                reading::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_cleans_database_disabling_triggers(backend: impl Backend) {
+        table! {
+            author (id) {
+                id -> Int4
+            }
+        }
+
+        table! {
+            publication (id) {
+                id -> Int4
+            }
+        }
+
+        table! {
+            review (id) {
+                id -> Int4
+            }
+        }
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            // Create linked tables, with review excluded from cleaning
+            let config = get_privileged_postgres_config();
+            let conn = &mut AsyncPgConnection::establish(
+                config.privileged_database_connection_url(db_name).as_str(),
+            )
+            .await
+            .unwrap();
+            conn.batch_execute(
+                "CREATE TABLE author(id INT PRIMARY KEY); \
+                 CREATE TABLE publication(id INT PRIMARY KEY, author_id INT NOT NULL REFERENCES author(id)); \
+                 CREATE TABLE review(id INT PRIMARY KEY, author_id INT NOT NULL REFERENCES author(id)); \
+                 INSERT INTO author VALUES (1); INSERT INTO publication VALUES (1, 1); INSERT INTO review VALUES (1, 1)",
+            )
+            .await
+            .unwrap();
+
+            backend.clean(db_id).await.unwrap();
+
+            // linked tables must be empty
+            assert_eq!(
+                author::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
+            assert_eq!(
+                publication::table
+                    .count()
+                    .get_result::<i64>(conn)
+                    .await
+                    .unwrap(),
+                0
+            );
+
+            // excluded referencing table must be left untouched
+            assert_eq!(
+                review::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_cleans_tables_with_quoted_names(backend: impl Backend) {
+        table! {
+            #[sql_name = "User"]
+            user_table (id) {
+                id -> Int4
+            }
+        }
+
+        table! {
+            #[sql_name = "select"]
+            select_table (id) {
+                id -> Int4
+            }
+        }
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            // Create tables with mixed-case and reserved names
+            let config = get_privileged_postgres_config();
+            let conn = &mut AsyncPgConnection::establish(
+                config.privileged_database_connection_url(db_name).as_str(),
+            )
+            .await
+            .unwrap();
+            let insert = r#"INSERT INTO "User" DEFAULT VALUES; INSERT INTO "select" DEFAULT VALUES"#;
+            conn.batch_execute(
+                r#"CREATE TABLE "User"(id SERIAL PRIMARY KEY); CREATE TABLE "select"(id SERIAL PRIMARY KEY)"#,
+            )
+            .await
+            .unwrap();
+
+            for clean_tables in [false, true] {
+                conn.batch_execute(insert).await.unwrap();
+
+                if clean_tables {
+                    backend
+                        .clean_tables(db_id, &["User", "select"])
+                        .await
+                        .unwrap();
+                } else {
+                    backend.clean(db_id).await.unwrap();
+                }
+
+                // tables must be empty
+                assert_eq!(
+                    user_table::table
+                        .count()
+                        .get_result::<i64>(conn)
+                        .await
+                        .unwrap(),
+                    0
+                );
+                assert_eq!(
+                    select_table::table
+                        .count()
+                        .get_result::<i64>(conn)
+                        .await
+                        .unwrap(),
+                    0
+                );
+            }
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_creates_database_owned_by_owner(backend: impl Backend, owner: &str) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            // database must be owned by owner role
+            assert_eq!(get_database_owner(db_name, conn).await, owner);
+
+            let db_conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let db_conn = &mut db_conn_pool.get().await.unwrap();
+
+            // entities must be owned by owner role
+            assert_eq!(
+                pg_tables::table
+                    .filter(pg_tables::tablename.eq("book"))
+                    .select(pg_tables::tableowner)
+                    .get_result::<String>(db_conn)
+                    .await
+                    .unwrap(),
+                owner
+            );
+
+            // restricted role must still be able to use entities
+            insert_books(1, db_conn).await;
+
+            // database must be cleaned as owner role
+            backend.clean(db_id).await.unwrap();
+            assert_eq!(
+                book::table
+                    .count()
+                    .get_result::<i64>(db_conn)
+                    .await
+                    .unwrap(),
+                0
+            );
+
+            // drop database and roles, terminating remaining connections
+            for stmt in [
+                postgres::force_drop_database(db_name),
+                postgres::drop_role(db_name),
+                postgres::drop_role(owner),
+            ] {
+                sql_query(stmt).execute(conn).await.unwrap();
+            }
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_cleans_database_restarting_identity(backend: impl Backend) {
+        const NUM_BOOKS: i64 = 3;
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            insert_books(NUM_BOOKS, conn).await;
+
+            backend.clean(db_id).await.unwrap();
+
+            insert_books(NUM_BOOKS, conn).await;
+
+            // identities must start from 1 again
+            assert_eq!(
+                book::table
+                    .select(book::id)
+                    .order(book::id)
+                    .load::<i32>(conn)
+                    .await
+                    .unwrap(),
+                (1..=3).collect::<Vec<_>>()
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_resets_sequences(backend: impl Backend) {
+        const NUM_BOOKS: i64 = 3;
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // deleting rows must not restart identities
+            insert_books(NUM_BOOKS, conn).await;
+            delete(book::table).execute(conn).await.unwrap();
+
+            backend.reset_sequences(db_id).await.unwrap();
+
+            insert_books(NUM_BOOKS, conn).await;
+
+            // identities must start from 1 again
+            assert_eq!(
+                book::table
+                    .select(book::id)
+                    .order(book::id)
+                    .load::<i32>(conn)
+                    .await
+                    .unwrap(),
+                (1..=3).collect::<Vec<_>>()
+            );
+
+            // rows must be preserved
+            backend.reset_sequences(db_id).await.unwrap();
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                NUM_BOOKS
+            );
+        }
+        .lock_read()
+        .await;
     }
 
-    pub async fn test_backend_drops_previous_databases<B: Backend>(
-        default: B,
-        enabled: B,
-        disabled: B,
-    ) {
-        const NUM_DBS: i64 = 3;
+    pub async fn test_backend_cleans_database_except_excluded_tables(backend: impl Backend) {
+        const NUM_BOOKS: i64 = 3;
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
 
         async {
-            let conn_pool = get_privileged_connection_pool().await;
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
             let conn = &mut conn_pool.get().await.unwrap();
 
-            for (backend, cleans) in [(default, true), (enabled, true), (disabled, false)] {
-                let db_names = create_databases(NUM_DBS, conn_pool).await;
-                assert_eq!(count_databases(&db_names, conn).await, NUM_DBS);
-                backend.init().await.unwrap();
-                assert_eq!(
-                    count_databases(&db_names, conn).await,
-                    if cleans { 0 } else { NUM_DBS }
-                );
-            }
+            insert_books(NUM_BOOKS, conn).await;
+            insert_into(dummy::table)
+                .default_values()
+                .execute(conn)
+                .await
+                .unwrap();
+
+            backend.clean(db_id).await.unwrap();
+
+            // there must be no books
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
+
+            // excluded tables must be untouched
+            assert_eq!(
+                dummy::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
         }
-        .lock_drop()
+        .lock_read()
         .await;
     }
 
-    pub async fn test_backend_creates_database_with_restricted_privileges(backend: impl Backend) {
+    pub async fn test_backend_cleans_database_with_many_tables(backend: impl Backend) {
+        const NUM_TABLES: usize = 50;
+
+        #[derive(QueryableByName)]
+        struct RowCount {
+            #[diesel(sql_type = diesel::sql_types::BigInt)]
+            count: i64,
+        }
+
         let db_id = Uuid::new_v4();
         let db_name = get_db_name(db_id);
         let db_name = db_name.as_str();
 
         async {
-            // privileged operations
-            {
-                let conn_pool = get_privileged_connection_pool().await;
-                let conn = &mut conn_pool.get().await.unwrap();
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
 
-                // database must not exist
-                assert!(!database_exists(db_name, conn).await);
+            let database_url =
+                get_privileged_postgres_config().privileged_database_connection_url(db_name);
+            let conn = &mut AsyncPgConnection::establish(database_url.as_str())
+                .await
+                .unwrap();
 
-                // database must exist after creating through backend
-                backend.init().await.unwrap();
-                backend.create(db_id, true).await.unwrap();
-                assert!(database_exists(db_name, conn).await);
+            // create tables with a row each
+            for i in 0..NUM_TABLES {
+                sql_query(format!("CREATE TABLE table_{i}(id SERIAL PRIMARY KEY)"))
+                    .execute(conn)
+                    .await
+                    .unwrap();
+                sql_query(format!("INSERT INTO table_{i} DEFAULT VALUES"))
+                    .execute(conn)
+                    .await
+                    .unwrap();
             }
 
-            // restricted operations
-            {
-                let conn_pool = &mut create_restricted_connection_pool(db_name).await;
-                let conn = &mut conn_pool.get().await.unwrap();
+            let count_rows = (0..NUM_TABLES)
+                .map(|i| format!("(SELECT count(*) FROM table_{i})"))
+                .collect::<Vec<_>>()
+                .join(" + ");
+            let count_rows = format!("SELECT {count_rows} AS count");
 
-                // DDL statements must fail
-                for stmt in DDL_STATEMENTS {
-                    assert!(sql_query(stmt).execute(conn).await.is_err());
-                }
+            assert_eq!(
+                sql_query(count_rows.as_str())
+                    .get_result::<RowCount>(conn)
+                    .await
+                    .unwrap()
+                    .count,
+                i64::try_from(NUM_TABLES).unwrap()
+            );
 
-                // DML statements must succeed
-                for stmt in DML_STATEMENTS {
-                    assert!(sql_query(stmt).execute(conn).await.is_ok());
-                }
-            }
+            // all tables must be truncated across batches
+            backend.clean(db_id).await.unwrap();
+            assert_eq!(
+                sql_query(count_rows.as_str())
+                    .get_result::<RowCount>(conn)
+                    .await
+                    .unwrap()
+                    .count,
+                0
+            );
         }
         .lock_read()
         .await;
     }
 
-    pub async fn test_backend_creates_database_with_unrestricted_privileges(backend: impl Backend) {
+    pub async fn test_backend_cleans_subset_of_tables(backend: impl Backend) {
+        const NUM_BOOKS: i64 = 3;
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
         async {
-            {
-                let db_id = Uuid::new_v4();
-                let db_name = get_db_name(db_id);
-                let db_name = db_name.as_str();
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
 
-                // privileged operations
-                {
-                    let conn_pool = get_privileged_connection_pool().await;
-                    let conn = &mut conn_pool.get().await.unwrap();
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
 
-                    // database must not exist
-                    assert!(!database_exists(db_name, conn).await);
+            insert_books(NUM_BOOKS, conn).await;
+            insert_into(dummy::table)
+                .default_values()
+                .execute(conn)
+                .await
+                .unwrap();
 
-                    // database must exist after creating through backend
-                    backend.init().await.unwrap();
-                    backend.create(db_id, false).await.unwrap();
-                    assert!(database_exists(db_name, conn).await);
-                }
+            backend.clean_tables(db_id, &["book"]).await.unwrap();
 
-                // DML statements must succeed
-                {
-                    let conn_pool = create_restricted_connection_pool(db_name).await;
-                    let conn = &mut conn_pool.get().await.unwrap();
-                    for stmt in DML_STATEMENTS {
-                        let result = sql_query(stmt).execute(conn).await;
-                        assert!(result.is_ok());
-                    }
-                }
-            }
+            // there must be no books
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
 
-            // DDL statements must succeed
-            try_join_all(DDL_STATEMENTS.iter().map(|stmt| {
-                let backend = &backend;
-                async move {
-                    let db_id = Uuid::new_v4();
-                    let db_name = get_db_name(db_id);
-                    let db_name = db_name.as_str();
+            // other tables must be untouched
+            assert_eq!(
+                dummy::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
 
-                    backend.create(db_id, false).await.unwrap();
-                    let conn_pool = create_restricted_connection_pool(db_name).await;
-                    let conn = &mut conn_pool.get().await.unwrap();
+            // unknown tables must be rejected
+            assert!(matches!(
+                backend.clean_tables(db_id, &["dummy", "author"]).await,
+                Err(BackendError::UnknownTable(table_name)) if table_name == "author"
+            ));
 
-                    sql_query(*stmt).execute(conn).await
-                }
-            }))
-            .await
-            .unwrap();
+            // no table must be cleaned upon rejection
+            assert_eq!(
+                dummy::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
         }
         .lock_read()
         .await;
     }
 
-    pub async fn test_backend_cleans_database_with_tables(backend: impl Backend) {
+    pub async fn test_backend_keeps_connection_after_failed_clean(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
+
+            let conn_pool = &mut create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // hold locks on table and its sequence until statements time out
+            conn.batch_execute("BEGIN; INSERT INTO book (title) VALUES ('Title')")
+                .await
+                .unwrap();
+
+            // cleaning must fail
+            assert!(backend.clean_tables(db_id, &["book"]).await.is_err());
+            assert!(backend.reset_sequences(db_id).await.is_err());
+            assert!(backend.clean(db_id).await.is_err());
+
+            conn.batch_execute("ROLLBACK").await.unwrap();
+
+            // database must still be cleaned and dropped
+            backend.clean_tables(db_id, &["book"]).await.unwrap();
+            backend.reset_sequences(db_id).await.unwrap();
+            backend.clean(db_id).await.unwrap();
+            backend.drop(db_id, true).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_rebuilds_connection_pool(backend: impl Backend) {
         const NUM_BOOKS: i64 = 3;
 
         let db_id = Uuid::new_v4();
@@ -572,44 +3632,185 @@ pub(super) mod tests {
 
         async {
             backend.init().await.unwrap();
-            backend.create(db_id, true).await.unwrap();
+            let db_conn_pool = backend.create(db_id, true).await.unwrap();
 
             let conn_pool = &mut create_restricted_connection_pool(db_name).await;
             let conn = &mut conn_pool.get().await.unwrap();
 
             insert_books(NUM_BOOKS, conn).await;
 
-            // there must be books
+            // connection pool must be rebuilt
+            drop(db_conn_pool);
+            backend.rebuild_pool(db_id).await.unwrap();
+
+            // books must remain since database is not recreated
             assert_eq!(
                 book::table.count().get_result::<i64>(conn).await.unwrap(),
                 NUM_BOOKS
             );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_cleans_database_without_tables(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
 
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, true).await.unwrap();
             backend.clean(db_id).await.unwrap();
+        }
+        .lock_read()
+        .await;
+    }
 
-            // there must be no books
-            assert_eq!(
-                book::table.count().get_result::<i64>(conn).await.unwrap(),
-                0
-            );
+    pub async fn test_backend_drops_database(backend: impl Backend, restricted: bool) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        async {
+            // database must exist
+            backend.init().await.unwrap();
+            backend.create(db_id, restricted).await.unwrap();
+            assert!(database_exists(db_name, conn).await);
+
+            // database must not exist
+            backend.drop(db_id, restricted).await.unwrap();
+            assert!(!database_exists(db_name, conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_checks_database_existence(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+
+        async {
+            // database must not exist
+            backend.init().await.unwrap();
+            assert!(!backend.database_exists(db_id).await.unwrap());
+
+            // database must exist
+            backend.create(db_id, true).await.unwrap();
+            assert!(backend.database_exists(db_id).await.unwrap());
+
+            // database must not exist
+            backend.drop(db_id, true).await.unwrap();
+            assert!(!backend.database_exists(db_id).await.unwrap());
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_fails_fast_dropping_locked_database(backend: impl Backend) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, false).await.unwrap();
+
+            // hold lock on database in open transaction
+            sql_query("BEGIN").execute(conn).await.unwrap();
+            sql_query(format!("COMMENT ON DATABASE {db_name} IS 'locked'"))
+                .execute(conn)
+                .await
+                .unwrap();
+
+            // drop must time out on lock instead of waiting for transaction
+            let start = Instant::now();
+            let result = backend.drop(db_id, false).await;
+            assert!(start.elapsed() < Duration::from_secs(5));
+            let Err(BackendError::Statement(stmt, _)) = result else {
+                panic!("dropping locked database must fail with statement");
+            };
+            assert_eq!(stmt, postgres::drop_database(db_name));
+            assert!(database_exists(db_name, conn).await);
+
+            // drop must succeed once lock is released
+            sql_query("ROLLBACK").execute(conn).await.unwrap();
+            backend.drop(db_id, false).await.unwrap();
+            assert!(!database_exists(db_name, conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_drops_database_within_grace_period(
+        backend: impl Backend,
+        grace_period: Duration,
+    ) {
+        const RELEASE_DELAY: Duration = Duration::from_millis(500);
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
+
+        let privileged_conn_pool = get_privileged_connection_pool().await;
+        let privileged_conn = &mut privileged_conn_pool.get().await.unwrap();
+
+        async {
+            backend.init().await.unwrap();
+            backend.create(db_id, false).await.unwrap();
+
+            // connection to database must be held until released within grace period
+            let conn_pool = create_restricted_connection_pool(db_name).await;
+            let conn = conn_pool.get_owned().await.unwrap();
+            let release = async move {
+                sleep(RELEASE_DELAY).await;
+                drop(conn);
+                drop(conn_pool);
+            };
+
+            // database must be dropped once connection is released without being forced
+            let start = Instant::now();
+            let (result, ()) = tokio::join!(backend.drop(db_id, false), release);
+            result.unwrap();
+            assert!(start.elapsed() < grace_period);
+            assert!(!database_exists(db_name, privileged_conn).await);
         }
         .lock_read()
         .await;
     }
 
-    pub async fn test_backend_cleans_database_without_tables(backend: impl Backend) {
+    pub async fn test_backend_analyzes_created_database(backend: impl Backend, restricted: bool) {
         let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+        let db_name = db_name.as_str();
 
         async {
             backend.init().await.unwrap();
-            backend.create(db_id, true).await.unwrap();
-            backend.clean(db_id).await.unwrap();
+            backend.create(db_id, restricted).await.unwrap();
+
+            let conn_pool = create_restricted_connection_pool(db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            // statistics must have been collected for created tables
+            assert!(
+                select(exists(
+                    pg_class::table
+                        .filter(pg_class::relname.eq("book"))
+                        .filter(pg_class::reltuples.ge(0.0))
+                ))
+                .get_result::<bool>(conn)
+                .await
+                .unwrap()
+            );
         }
         .lock_read()
         .await;
     }
 
-    pub async fn test_backend_drops_database(backend: impl Backend, restricted: bool) {
+    pub async fn test_backend_reports_existing_database(backend: impl Backend) {
         let db_id = Uuid::new_v4();
         let db_name = get_db_name(db_id);
         let db_name = db_name.as_str();
@@ -618,14 +3819,47 @@ pub(super) mod tests {
         let conn = &mut conn_pool.get().await.unwrap();
 
         async {
-            // database must exist
+            backend.init().await.unwrap();
+
+            // database with same name must already exist
+            sql_query(format!("CREATE DATABASE {db_name}"))
+                .execute(conn)
+                .await
+                .unwrap();
+
+            // name collision must be reported
+            assert!(matches!(
+                backend.create(db_id, false).await,
+                Err(BackendError::DatabaseExists(name)) if name == db_name
+            ));
+
+            sql_query(format!("DROP DATABASE {db_name}"))
+                .execute(conn)
+                .await
+                .unwrap();
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_backend_runs_hook_before_drop(
+        backend: impl Backend,
+        restricted: bool,
+        dropped_db_names: Arc<Mutex<Vec<String>>>,
+    ) {
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+
+        async {
             backend.init().await.unwrap();
             backend.create(db_id, restricted).await.unwrap();
-            assert!(database_exists(db_name, conn).await);
 
-            // database must not exist
+            // hook must not have run
+            assert!(dropped_db_names.lock().is_empty());
+
+            // hook must have run for dropped database
             backend.drop(db_id, restricted).await.unwrap();
-            assert!(!database_exists(db_name, conn).await);
+            assert_eq!(*dropped_db_names.lock(), vec![db_name]);
         }
         .lock_read()
         .await;
@@ -643,7 +3877,7 @@ pub(super) mod tests {
             let conn = &mut conn_pool.get().await.unwrap();
 
             for (backend, cleans) in [(default, true), (enabled, true), (disabled, false)] {
-                let db_names = create_databases(NUM_DBS, conn_pool).await;
+                let db_names = create_databases(DEFAULT_DB_NAME_PREFIX, NUM_DBS, conn_pool).await;
                 assert_eq!(count_databases(&db_names, conn).await, NUM_DBS);
                 backend.create_database_pool().await.unwrap();
                 assert_eq!(
@@ -656,6 +3890,128 @@ pub(super) mod tests {
         .await;
     }
 
+    pub async fn test_pool_caps_checked_out_databases(backend: impl Backend) {
+        const WAIT: Duration = Duration::from_millis(500);
+
+        async {
+            let db_pool = backend
+                .create_database_pool()
+                .await
+                .unwrap()
+                .max_checked_out_databases(2);
+
+            let conn_pool_1 = db_pool.pull_immutable().await;
+            let _conn_pool_2 = db_pool.pull_immutable().await;
+
+            // must wait at the cap
+            assert!(timeout(WAIT, db_pool.pull_immutable()).await.is_err());
+            assert!(timeout(WAIT, db_pool.create_mutable()).await.is_err());
+
+            // must pull once a connection pool is released
+            drop(conn_pool_1);
+            assert!(timeout(WAIT, db_pool.pull_immutable()).await.is_ok());
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_pool_reports_provision_timings(backend: impl Backend) {
+        const NUM_DBS: i64 = 3;
+
+        async {
+            let conn_pool = get_privileged_connection_pool().await;
+            create_databases(DEFAULT_DB_NAME_PREFIX, NUM_DBS, conn_pool).await;
+
+            let db_pool = backend.create_database_pool().await.unwrap();
+            let timings = db_pool.provision_timings();
+
+            // both phases must have been timed
+            assert!(timings.init > Duration::ZERO);
+            assert!(timings.initial_creations > Duration::ZERO);
+        }
+        .lock_drop()
+        .await;
+    }
+
+    pub async fn test_pool_creates_initial_databases(backend: impl Backend, initial_size: usize) {
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+
+            // initial databases must have been created
+            assert_eq!(
+                count_all_databases(conn).await,
+                i64::try_from(initial_size).unwrap()
+            );
+
+            // initial databases must be available for reuse
+            assert_eq!(db_pool.stats().available, initial_size);
+        }
+        .lock_drop()
+        .await;
+    }
+
+    pub async fn test_pool_exposes_database_name(backend: impl Backend) {
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+
+            // names of pulled and created databases must refer to existing databases
+            let reusable_conn_pool = db_pool.pull_immutable().await;
+            let single_use_conn_pool = db_pool.create_mutable().await.unwrap();
+            for db_name in [reusable_conn_pool.db_name(), single_use_conn_pool.db_name()] {
+                assert!(db_name.starts_with("db_pool_"));
+                assert!(database_exists(db_name.as_str(), conn).await);
+            }
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_pool_pulls_single_connection<B: Backend>(
+        backend: B,
+        run_query: impl AsyncFnOnce(&mut B::Connection),
+    ) {
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+
+            // connection must be usable
+            let mut single_use_conn = db_pool.pull_connection().await.unwrap();
+            run_query(&mut single_use_conn).await;
+
+            // database must exist while connection is held
+            let db_name = single_use_conn.db_name();
+            assert!(database_exists(db_name.as_str(), conn).await);
+
+            // database must be dropped along with connection
+            drop(single_use_conn);
+            assert!(!database_exists(db_name.as_str(), conn).await);
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_pool_exposes_privilege_restriction(backend: impl Backend) {
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+
+            // pulled databases must be restricted unlike created ones
+            let reusable_conn_pool = db_pool.pull_immutable().await;
+            let single_use_conn_pool = db_pool.create_mutable().await.unwrap();
+            assert!(reusable_conn_pool.is_restricted());
+            assert!(!single_use_conn_pool.is_restricted());
+        }
+        .lock_read()
+        .await;
+    }
+
     pub async fn test_pool_drops_created_restricted_databases(backend: impl Backend) {
         const NUM_DBS: i64 = 3;
 
@@ -690,6 +4046,31 @@ pub(super) mod tests {
         .await;
     }
 
+    pub async fn test_pool_closes_and_drops_databases(backend: impl Backend) {
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+
+            // create databases
+            {
+                let _conn_pools = join_all((0..3).map(|_| db_pool.pull_immutable())).await;
+            }
+
+            // there must be databases
+            assert_eq!(count_all_databases(conn).await, 3);
+
+            // must drop databases
+            db_pool.close().await.unwrap();
+
+            // there must be no databases
+            assert_eq!(count_all_databases(conn).await, 0);
+        }
+        .lock_drop()
+        .await;
+    }
+
     pub async fn test_pool_drops_created_unrestricted_database(backend: impl Backend) {
         let conn_pool = get_privileged_connection_pool().await;
         let conn = &mut conn_pool.get().await.unwrap();
@@ -720,4 +4101,281 @@ pub(super) mod tests {
         .lock_drop()
         .await;
     }
+
+    pub async fn test_pool_rebuilds_connection_pools(backend: impl Backend) {
+        let conn_pool = get_privileged_connection_pool().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+
+            // fetch connection pools
+            let mut reusable_conn_pool = db_pool.pull_immutable().await;
+            let mut single_use_conn_pool = db_pool.create_mutable().await.unwrap();
+
+            // there must be two databases
+            assert_eq!(count_all_databases(conn).await, 2);
+
+            // must rebuild connection pools
+            reusable_conn_pool.rebuild_pool().await.unwrap();
+            single_use_conn_pool.rebuild_pool().await.unwrap();
+
+            // there must still be two databases
+            assert_eq!(count_all_databases(conn).await, 2);
+        }
+        .lock_drop()
+        .await;
+    }
+
+    pub async fn test_pool_cleans_database_on_demand(backend: impl Backend) {
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+            let mut reusable_conn_pool = db_pool.pull_immutable().await;
+
+            let conn_pool = create_restricted_connection_pool(&reusable_conn_pool.db_name()).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+
+            insert_books(2, conn).await;
+
+            // must clean database while handle is held
+            reusable_conn_pool.clean().await.unwrap();
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
+
+            // handle must remain usable
+            insert_books(1, conn).await;
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                1
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_pool_snapshots_and_restores_database(backend: impl Backend) {
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+            let mut reusable_conn_pool = db_pool.pull_immutable().await;
+            let db_name = reusable_conn_pool.db_name();
+
+            // seed state and mark it as restore point
+            {
+                let conn_pool = create_restricted_connection_pool(&db_name).await;
+                let conn = &mut conn_pool.get().await.unwrap();
+                insert_books(2, conn).await;
+            }
+            reusable_conn_pool.snapshot().await.unwrap();
+
+            // mutate state
+            {
+                let conn_pool = create_restricted_connection_pool(&db_name).await;
+                let conn = &mut conn_pool.get().await.unwrap();
+                insert_books(3, conn).await;
+                assert_eq!(
+                    book::table.count().get_result::<i64>(conn).await.unwrap(),
+                    5
+                );
+            }
+
+            // original state must return
+            reusable_conn_pool.restore().await.unwrap();
+            let conn_pool = create_restricted_connection_pool(&db_name).await;
+            let conn = &mut conn_pool.get().await.unwrap();
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                2
+            );
+
+            // restored database must remain cleanable
+            reusable_conn_pool.clean().await.unwrap();
+            assert_eq!(
+                book::table.count().get_result::<i64>(conn).await.unwrap(),
+                0
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_pool_rejects_pull_after_shutdown(backend: impl Backend) {
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+
+            // must pull connection pool
+            db_pool.try_pull_immutable().await.unwrap();
+
+            db_pool.shutdown();
+
+            // must fail to pull or create connection pools
+            assert!(matches!(
+                db_pool.try_pull_immutable().await,
+                Err(BackendError::PoolShutdown)
+            ));
+            assert!(matches!(
+                db_pool.create_mutable().await,
+                Err(BackendError::PoolShutdown)
+            ));
+
+            // must panic when pulling without handling shutdown
+            assert!(
+                AssertUnwindSafe(db_pool.pull_immutable())
+                    .catch_unwind()
+                    .await
+                    .is_err()
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_pool_reports_stats(backend: impl Backend) {
+        const NUM_DBS: usize = 3;
+
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+
+            // pulled databases must be in use
+            let conn_pools = join_all((0..NUM_DBS).map(|_| db_pool.pull_immutable())).await;
+            assert_eq!(
+                db_pool.stats(),
+                PoolStats {
+                    created: NUM_DBS,
+                    in_use: NUM_DBS,
+                    available: 0,
+                }
+            );
+
+            // returned databases must be available
+            drop(conn_pools);
+            assert_eq!(
+                db_pool.stats(),
+                PoolStats {
+                    created: NUM_DBS,
+                    in_use: 0,
+                    available: NUM_DBS,
+                }
+            );
+        }
+        .lock_read()
+        .await;
+    }
+
+    pub async fn test_pool_gives_up_dropping_database_after_timeout(
+        backend: impl Backend,
+        drop_timeout: Duration,
+    ) {
+        let privileged_conn_pool = get_privileged_connection_pool().await;
+        let privileged_conn = &mut privileged_conn_pool.get().await.unwrap();
+
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+            let conn_pool = db_pool.create_mutable().await.unwrap();
+
+            let db_name = pg_database::table
+                .select(pg_database::datname)
+                .filter(pg_database::datname.like("db_pool_%"))
+                .first::<String>(privileged_conn)
+                .await
+                .unwrap();
+
+            // connection to database must keep it from being dropped within grace period
+            let restricted_conn_pool = create_restricted_connection_pool(&db_name).await;
+            let restricted_conn = restricted_conn_pool.get_owned().await.unwrap();
+
+            // dropping must be given up after timeout, leaving database behind
+            let start = Instant::now();
+            drop(conn_pool);
+            assert!(start.elapsed() < drop_timeout * 2);
+            assert!(database_exists(&db_name, privileged_conn).await);
+
+            drop(restricted_conn);
+            drop(restricted_conn_pool);
+            sql_query(postgres::drop_database(&db_name))
+                .execute(privileged_conn)
+                .await
+                .unwrap();
+        }
+        .lock_drop()
+        .await;
+    }
+
+    pub async fn test_pool_keeps_database_on_panic(backend: impl Backend) {
+        let privileged_conn_pool = get_privileged_connection_pool().await;
+        let privileged_conn = &mut privileged_conn_pool.get().await.unwrap();
+
+        async {
+            let db_pool = backend.create_database_pool().await.unwrap();
+
+            // panic must occur while database is pulled
+            let result = AssertUnwindSafe(async {
+                let _conn_pool = db_pool.pull_immutable().await;
+                panic!("test failure");
+            })
+            .catch_unwind()
+            .await;
+            assert!(result.is_err());
+
+            // database must not be returned for reuse
+            assert_eq!(db_pool.stats().available, 0);
+
+            // database must be kept after database pool is dropped
+            drop(db_pool);
+            let db_name = pg_database::table
+                .select(pg_database::datname)
+                .filter(pg_database::datname.like("db_pool_%"))
+                .first::<String>(privileged_conn)
+                .await
+                .unwrap();
+
+            sql_query(postgres::force_drop_database(&db_name))
+                .execute(privileged_conn)
+                .await
+                .unwrap();
+            sql_query(postgres::drop_role(&db_name))
+                .execute(privileged_conn)
+                .await
+                .unwrap();
+        }
+        .lock_drop()
+        .await;
+    }
+
+    #[test]
+    fn role_attributes_report_missing_privileges() {
+        let attributes = RoleAttributes {
+            superuser: false,
+            create_db: false,
+            create_role: true,
+        };
+        assert_eq!(attributes.missing_privileges(), vec!["CREATEDB"]);
+
+        let attributes = RoleAttributes {
+            superuser: false,
+            create_db: false,
+            create_role: false,
+        };
+        assert_eq!(
+            attributes.missing_privileges(),
+            vec!["CREATEDB", "CREATEROLE"]
+        );
+
+        // superuser bypasses missing attributes
+        let attributes = RoleAttributes {
+            superuser: true,
+            create_db: false,
+            create_role: false,
+        };
+        assert!(attributes.missing_privileges().is_empty());
+    }
+
+    #[test]
+    fn table_names_match_with_or_without_schema() {
+        assert!(matches_table_name("invoice", "billing", "invoice"));
+        assert!(matches_table_name("billing.invoice", "billing", "invoice"));
+        assert!(!matches_table_name("public.invoice", "billing", "invoice"));
+        assert!(!matches_table_name("book", "billing", "invoice"));
+    }
 }
@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+
+use crate::common::statement::postgres;
+
+pub(super) type CleanTable = dyn Fn(&str) -> Option<Vec<String>> + Send + Sync + 'static;
+
+/// How tables are truncated when cleaning a database
+#[derive(Default)]
+pub enum TruncateMode {
+    /// One ``TRUNCATE`` statement per table
+    #[default]
+    PerTable,
+    /// A single ``TRUNCATE`` statement per batch of tables
+    MultiTable,
+}
+
+/// How foreign keys between tables are handled when cleaning a database
+#[derive(Default)]
+pub enum FkHandling {
+    /// Truncate with ``CASCADE``, also truncating tables referencing cleaned ones
+    #[default]
+    Cascade,
+    /// Delete rows with foreign key triggers disabled through ``session_replication_role``,
+    /// leaving tables referencing cleaned ones untouched
+    ///
+    /// Requires a superuser and does not restart identity columns
+    DisableTriggers,
+}
+
+impl TruncateMode {
+    pub(super) fn statements<'a>(
+        &self,
+        table_names: &[(&str, &str)],
+        fk_handling: &FkHandling,
+    ) -> Vec<Cow<'a, str>> {
+        match fk_handling {
+            FkHandling::Cascade => match self {
+                Self::PerTable => table_names
+                    .iter()
+                    .map(|(schema_name, table_name)| {
+                        postgres::truncate_table(schema_name, table_name).into()
+                    })
+                    .collect(),
+                Self::MultiTable => vec![postgres::truncate_tables(table_names).into()],
+            },
+            FkHandling::DisableTriggers => {
+                // Bracket deletes with session role statements within the same batch
+                let mut stmts = vec![postgres::SET_REPLICA_SESSION_ROLE.into()];
+                stmts.extend(table_names.iter().map(|(schema_name, table_name)| {
+                    postgres::delete_from_table(schema_name, table_name).into()
+                }));
+                stmts.push(postgres::RESET_SESSION_ROLE.into());
+                stmts
+            }
+        }
+    }
+}
@@ -1,15 +1,19 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use super::error::Error;
+use crate::util::get_db_name;
+
+use super::error::{Error, Operation};
 
 /// Backend trait
 #[async_trait]
 pub trait Backend: Sized + Send + Sync + 'static {
     /// Connection pool type that implements [`Send`](https://doc.rust-lang.org/std/marker/trait.Send.html)
     type Pool: Send;
+    /// Connection type that implements [`Send`](https://doc.rust-lang.org/std/marker/trait.Send.html)
+    type Connection: Send;
 
     /// Connection pool build error type that implements [`Debug`](https://doc.rust-lang.org/std/fmt/trait.Debug.html) and [`Send`](https://doc.rust-lang.org/std/marker/trait.Send.html)
     type BuildError: Debug + Send;
@@ -35,16 +39,134 @@ pub trait Backend: Sized + Send + Sync + 'static {
         Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>,
     >;
 
+    /// Creates a database, establishing a single connection to it instead of building a connection pool
+    async fn create_connection(
+        &self,
+        db_id: Uuid,
+        _restrict_privileges: bool,
+    ) -> Result<
+        Self::Connection,
+        Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>,
+    > {
+        Err(Error::Unsupported(Operation::Create(
+            self.get_database_name(db_id),
+        )))
+    }
+
     /// Cleans a database
     async fn clean(
         &self,
         db_id: Uuid,
     ) -> Result<(), Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>>;
 
+    /// Cleans only the given tables of a database
+    async fn clean_tables(
+        &self,
+        db_id: Uuid,
+        _table_names: &[&str],
+    ) -> Result<(), Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>>
+    {
+        Err(Error::Unsupported(Operation::Clean(
+            self.get_database_name(db_id),
+        )))
+    }
+
+    /// Restarts all sequences of a database without cleaning its tables
+    async fn reset_sequences(
+        &self,
+        db_id: Uuid,
+    ) -> Result<(), Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>>
+    {
+        Err(Error::Unsupported(Operation::Clean(
+            self.get_database_name(db_id),
+        )))
+    }
+
+    /// Rebuilds the connection pool of a database without recreating the database
+    async fn rebuild_pool(
+        &self,
+        db_id: Uuid,
+    ) -> Result<
+        Self::Pool,
+        Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>,
+    > {
+        Err(Error::Unsupported(Operation::RebuildPool(
+            self.get_database_name(db_id),
+        )))
+    }
+
+    /// Marks the current state of a database as a restore point, replacing any previous one
+    async fn snapshot(
+        &self,
+        db_id: Uuid,
+    ) -> Result<(), Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>>
+    {
+        Err(Error::Unsupported(Operation::Snapshot(
+            self.get_database_name(db_id),
+        )))
+    }
+
+    /// Restores a database to the state of its last snapshot
+    async fn restore(
+        &self,
+        db_id: Uuid,
+    ) -> Result<(), Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>>
+    {
+        Err(Error::Unsupported(Operation::Restore(
+            self.get_database_name(db_id),
+        )))
+    }
+
+    /// Returns whether a database currently exists without building a connection pool
+    async fn database_exists(
+        &self,
+        db_id: Uuid,
+    ) -> Result<
+        bool,
+        Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>,
+    > {
+        Err(Error::Unsupported(Operation::CheckExists(
+            self.get_database_name(db_id),
+        )))
+    }
+
     /// Drops a database
     async fn drop(
         &self,
         db_id: Uuid,
         is_restricted: bool,
     ) -> Result<(), Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>>;
+
+    /// Verifies that the backend is able to create databases, failing with the missing privileges otherwise
+    async fn verify(
+        &self,
+    ) -> Result<(), Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>>
+    {
+        Ok(())
+    }
+
+    /// Name of the database with the given ID
+    fn get_database_name(&self, db_id: Uuid) -> String {
+        get_db_name(db_id)
+    }
+
+    /// Whether to keep databases pulled by a panicking thread instead of cleaning and dropping them
+    fn get_keep_databases_on_panic(&self) -> bool {
+        false
+    }
+
+    /// Maximum number of retries with a newly generated name when a database name is already taken
+    fn get_max_create_retries(&self) -> usize {
+        0
+    }
+
+    /// Number of reusable databases to create eagerly when creating a database pool
+    fn get_initial_size(&self) -> usize {
+        0
+    }
+
+    /// Maximum duration to wait for a database to drop when its connection pool is dropped
+    fn get_drop_timeout(&self) -> Option<Duration> {
+        None
+    }
 }
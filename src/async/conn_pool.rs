@@ -1,18 +1,118 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+    time::Instant,
+};
 
+use tokio::sync::OwnedSemaphorePermit;
 use uuid::Uuid;
 
-use super::backend::{r#trait::Backend, Error as BackendError};
+use super::{
+    backend::{Error as BackendError, r#trait::Backend},
+    stats::Stats,
+};
 
-struct ConnectionPool<B: Backend> {
+struct ConnectionPool<B: Backend, T = <B as Backend>::Pool> {
     backend: Arc<B>,
     db_id: Uuid,
-    conn_pool: Option<B::Pool>,
+    conn_pool: Option<T>,
     is_restricted: bool,
+    stats: Arc<Stats>,
+    is_dropped: bool,
 }
 
-impl<B: Backend> Deref for ConnectionPool<B> {
-    type Target = B::Pool;
+impl<B: Backend> ConnectionPool<B> {
+    async fn new(
+        backend: Arc<B>,
+        stats: Arc<Stats>,
+        is_restricted: bool,
+    ) -> Result<Self, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        Self::create(backend, stats, is_restricted, |backend, db_id| async move {
+            backend.create(db_id, is_restricted).await
+        })
+        .await
+    }
+
+    async fn rebuild(
+        &mut self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Replace connection pool, tearing down the previous one
+        let conn_pool = self.backend.rebuild_pool(self.db_id).await?;
+        self.conn_pool = Some(conn_pool);
+        Ok(())
+    }
+}
+
+impl<B: Backend, T> ConnectionPool<B, T> {
+    async fn create<F>(
+        backend: Arc<B>,
+        stats: Arc<Stats>,
+        is_restricted: bool,
+        create: impl Fn(Arc<B>, Uuid) -> F,
+    ) -> Result<Self, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    where
+        F: Future<
+            Output = Result<
+                T,
+                BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+            >,
+        >,
+    {
+        let mut retries = 0;
+
+        loop {
+            let db_id = Uuid::new_v4();
+            let start = Instant::now();
+
+            match create(backend.clone(), db_id).await {
+                Ok(conn_pool) => {
+                    stats.record_create(start.elapsed());
+
+                    return Ok(Self {
+                        backend,
+                        db_id,
+                        conn_pool: Some(conn_pool),
+                        is_restricted,
+                        stats,
+                        is_dropped: false,
+                    });
+                }
+                // Retry with a newly generated name if the name is already taken
+                Err(BackendError::DatabaseExists(_))
+                    if retries < backend.get_max_create_retries() =>
+                {
+                    retries += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn db_name(&self) -> String {
+        self.backend.get_database_name(self.db_id)
+    }
+
+    fn is_restricted(&self) -> bool {
+        self.is_restricted
+    }
+
+    async fn close(
+        mut self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        self.conn_pool = None;
+        let result = (*self.backend).drop(self.db_id, self.is_restricted).await;
+        self.stats.record_drop();
+        // Skip dropping the database again when dropped
+        self.is_dropped = true;
+        result
+    }
+}
+
+impl<B: Backend, T> Deref for ConnectionPool<B, T> {
+    type Target = T;
 
     fn deref(&self) -> &Self::Target {
         self.conn_pool
@@ -21,44 +121,177 @@ impl<B: Backend> Deref for ConnectionPool<B> {
     }
 }
 
-impl<B: Backend> Drop for ConnectionPool<B> {
+impl<B: Backend, T> DerefMut for ConnectionPool<B, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.conn_pool
+            .as_mut()
+            .expect("conn_pool must always contain a [Some] value")
+    }
+}
+
+impl<B: Backend, T> Drop for ConnectionPool<B, T> {
     fn drop(&mut self) {
+        if self.is_dropped {
+            return;
+        }
         self.conn_pool = None;
+        if self.backend.get_keep_databases_on_panic() && std::thread::panicking() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                db_name = self.backend.get_database_name(self.db_id),
+                "keeping database of panicking thread"
+            );
+            self.stats.record_drop();
+            return;
+        }
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(async {
-                (*self.backend)
-                    .drop(self.db_id, self.is_restricted)
-                    .await
-                    .ok();
+                let drop_database = (*self.backend).drop(self.db_id, self.is_restricted);
+                if let Some(drop_timeout) = self.backend.get_drop_timeout() {
+                    // Leave the database behind to be dropped on the next initialization
+                    if tokio::time::timeout(drop_timeout, drop_database)
+                        .await
+                        .is_err()
+                    {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(
+                            db_id = %self.db_id,
+                            ?drop_timeout,
+                            "timed out dropping database, leaving it behind"
+                        );
+                    }
+                } else {
+                    drop_database.await.ok();
+                }
             });
         });
+        self.stats.record_drop();
+    }
+}
+
+/// Clean and hand-out generations of a reusable database
+#[derive(Default)]
+struct Generations {
+    handed_out: usize,
+    cleaned: usize,
+}
+
+impl Generations {
+    fn record_clean(&mut self) {
+        self.cleaned = self.handed_out;
+    }
+
+    fn record_hand_out(&mut self) {
+        debug_assert_eq!(
+            self.cleaned, self.handed_out,
+            "database must be cleaned before being handed out again"
+        );
+        self.handed_out += 1;
     }
 }
 
 /// Reusable connection pool wrapper
-pub struct ReusableConnectionPool<B: Backend>(ConnectionPool<B>);
+pub struct ReusableConnectionPool<B: Backend>(ConnectionPool<B>, Generations);
 
 impl<B: Backend> ReusableConnectionPool<B> {
     pub(crate) async fn new(
         backend: Arc<B>,
+        stats: Arc<Stats>,
     ) -> Result<Self, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
     {
-        let db_id = Uuid::new_v4();
-        let conn_pool = backend.create(db_id, true).await?;
+        ConnectionPool::new(backend, stats, true)
+            .await
+            .map(|conn_pool| Self(conn_pool, Generations::default()))
+    }
 
-        Ok(Self(ConnectionPool {
-            backend,
-            db_id,
-            conn_pool: Some(conn_pool),
-            is_restricted: true,
-        }))
+    /// Cleans the database on demand, keeping the same database for further use
+    pub async fn clean(
+        &mut self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        let start = Instant::now();
+        self.0.backend.clean(self.0.db_id).await?;
+        self.0.stats.record_clean(start.elapsed());
+        self.1.record_clean();
+        Ok(())
+    }
+
+    /// Drops the database, awaiting completion
+    pub(crate) async fn close(
+        self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        self.0.close().await
+    }
+
+    /// Asserts in debug builds that the database was cleaned since it was last handed out
+    pub(crate) fn record_hand_out(&mut self) {
+        self.1.record_hand_out();
+    }
+
+    /// Returns the name of the underlying database
+    #[must_use]
+    pub fn db_name(&self) -> String {
+        self.0.db_name()
     }
 
-    pub(crate) async fn clean(
+    /// Returns whether the underlying database was created with restricted privileges
+    #[must_use]
+    pub fn is_restricted(&self) -> bool {
+        self.0.is_restricted()
+    }
+
+    /// Cleans only the given tables, leaving all other tables intact
+    ///
+    /// Fails without cleaning any table if one of the given tables does not exist.
+    pub async fn clean_tables(
         &mut self,
+        table_names: &[&str],
     ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
     {
-        self.0.backend.clean(self.0.db_id).await
+        self.0.backend.clean_tables(self.0.db_id, table_names).await
+    }
+
+    /// Restarts all sequences, leaving table contents intact
+    pub async fn reset_sequences(
+        &mut self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        self.0.backend.reset_sequences(self.0.db_id).await
+    }
+
+    /// Replaces the connection pool with a newly created one without recreating the database
+    pub async fn rebuild_pool(
+        &mut self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        self.0.rebuild().await
+    }
+
+    /// Marks the current state of the database as a restore point, replacing any previous one
+    ///
+    /// Open connections to the database are terminated and the connection pool is replaced.
+    pub async fn snapshot(
+        &mut self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Replace connection pool whose connections were terminated, keeping it if rebuilding fails
+        let result = self.0.backend.snapshot(self.0.db_id).await;
+        let rebuilt = self.0.rebuild().await;
+        result.and(rebuilt)
+    }
+
+    /// Restores the database to the state of its last snapshot
+    ///
+    /// Open connections to the database are terminated and the connection pool is replaced.
+    pub async fn restore(
+        &mut self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        // Replace connection pool whose connections were terminated, keeping it if rebuilding fails
+        let result = self.0.backend.restore(self.0.db_id).await;
+        let rebuilt = self.0.rebuild().await;
+        result.and(rebuilt)
     }
 }
 
@@ -71,22 +304,41 @@ impl<B: Backend> Deref for ReusableConnectionPool<B> {
 }
 
 /// Single-use connection pool wrapper
-pub struct SingleUseConnectionPool<B: Backend>(ConnectionPool<B>);
+pub struct SingleUseConnectionPool<B: Backend>(ConnectionPool<B>, Option<OwnedSemaphorePermit>);
 
 impl<B: Backend> SingleUseConnectionPool<B> {
     pub(crate) async fn new(
         backend: Arc<B>,
+        stats: Arc<Stats>,
     ) -> Result<Self, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
     {
-        let db_id = Uuid::new_v4();
-        let conn_pool = backend.create(db_id, false).await?;
+        ConnectionPool::new(backend, stats, false)
+            .await
+            .map(|conn_pool| Self(conn_pool, None))
+    }
+
+    pub(crate) fn hold(&mut self, permit: OwnedSemaphorePermit) {
+        self.1 = Some(permit);
+    }
+
+    /// Returns the name of the underlying database
+    #[must_use]
+    pub fn db_name(&self) -> String {
+        self.0.db_name()
+    }
 
-        Ok(Self(ConnectionPool {
-            backend,
-            db_id,
-            conn_pool: Some(conn_pool),
-            is_restricted: false,
-        }))
+    /// Returns whether the underlying database was created with restricted privileges
+    #[must_use]
+    pub fn is_restricted(&self) -> bool {
+        self.0.is_restricted()
+    }
+
+    /// Replaces the connection pool with a newly created one without recreating the database
+    pub async fn rebuild_pool(
+        &mut self,
+    ) -> Result<(), BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        self.0.rebuild().await
     }
 }
 
@@ -97,3 +349,73 @@ impl<B: Backend> Deref for SingleUseConnectionPool<B> {
         &self.0
     }
 }
+
+/// Single-use connection wrapper, dropping its database once dropped
+pub struct SingleUseConnection<B: Backend>(
+    ConnectionPool<B, B::Connection>,
+    Option<OwnedSemaphorePermit>,
+);
+
+impl<B: Backend> SingleUseConnection<B> {
+    pub(crate) async fn new(
+        backend: Arc<B>,
+        stats: Arc<Stats>,
+    ) -> Result<Self, BackendError<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>
+    {
+        ConnectionPool::create(backend, stats, false, |backend, db_id| async move {
+            backend.create_connection(db_id, false).await
+        })
+        .await
+        .map(|conn| Self(conn, None))
+    }
+
+    pub(crate) fn hold(&mut self, permit: OwnedSemaphorePermit) {
+        self.1 = Some(permit);
+    }
+
+    /// Returns the name of the underlying database
+    #[must_use]
+    pub fn db_name(&self) -> String {
+        self.0.db_name()
+    }
+}
+
+impl<B: Backend> Deref for SingleUseConnection<B> {
+    type Target = B::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<B: Backend> DerefMut for SingleUseConnection<B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Generations;
+
+    #[test]
+    fn allows_hand_out_after_clean() {
+        let mut generations = Generations::default();
+
+        generations.record_hand_out();
+        generations.record_clean();
+        generations.record_hand_out();
+        generations.record_clean();
+        generations.record_hand_out();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "database must be cleaned before being handed out again")]
+    fn rejects_hand_out_without_clean() {
+        let mut generations = Generations::default();
+
+        generations.record_hand_out();
+        generations.record_hand_out();
+    }
+}
@@ -1,26 +1,158 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
+use futures::future::join_all;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 use super::{
     backend::{Error, r#trait::Backend},
-    conn_pool::{ReusableConnectionPool as ReusableConnectionPoolInner, SingleUseConnectionPool},
+    conn_pool::{
+        ReusableConnectionPool as ReusableConnectionPoolInner, SingleUseConnection,
+        SingleUseConnectionPool,
+    },
     object_pool::{ObjectPool, Reusable},
+    stats::Stats,
 };
 
 /// Wrapper for a reusable connection pool wrapped in a reusable object wrapper
 pub type ReusableConnectionPool<'a, B> = Reusable<'a, ReusableConnectionPoolInner<B>>;
 
+/// Database pool statistics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Number of reusable databases created
+    pub created: usize,
+    /// Number of reusable databases currently pulled
+    pub in_use: usize,
+    /// Number of reusable databases awaiting reuse
+    pub available: usize,
+}
+
+/// Durations of the phases of creating a database pool
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProvisionTimings {
+    /// Duration of initializing the backend, including dropping previous databases
+    pub init: Duration,
+    /// Duration of eagerly creating initial reusable databases
+    pub initial_creations: Duration,
+}
+
 /// Database pool
 pub struct DatabasePool<B: Backend> {
     backend: Arc<B>,
     object_pool: ObjectPool<ReusableConnectionPoolInner<B>>,
+    stats: Arc<Stats>,
+    provision_timings: ProvisionTimings,
+    checked_out_databases: Option<Arc<Semaphore>>,
+    #[cfg(feature = "tracing")]
+    report_summary_flag: bool,
+    is_shut_down: AtomicBool,
 }
 
 impl<B: Backend> DatabasePool<B> {
+    /// Log a summary of database pool activity when the database pool is dropped
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn report_summary(mut self, value: bool) -> Self {
+        self.report_summary_flag = value;
+        self
+    }
+
+    /// Caps the number of concurrently checked-out databases
+    ///
+    /// Pulled reusable databases and created single-use databases count against the cap
+    /// until their connection pool or connection is dropped, while pulls and creations wait at the cap.
+    /// Reusable databases kept available for reuse do not count against it,
+    /// so more databases than the cap may exist on the database server.
+    #[must_use]
+    pub fn max_checked_out_databases(mut self, value: usize) -> Self {
+        self.checked_out_databases = Some(Arc::new(Semaphore::new(value)));
+        self
+    }
+
+    async fn acquire_checkout(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.checked_out_databases {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore must never be closed"),
+            ),
+            None => None,
+        }
+    }
+
+    /// Shuts down the database pool
+    ///
+    /// Subsequent pulls and creations of connection pools fail with [`Error::PoolShutdown`],
+    /// except for [`pull_immutable`](Self::pull_immutable), which panics instead.
+    pub fn shutdown(&self) {
+        self.is_shut_down.store(true, Ordering::Release);
+    }
+
+    /// Shuts down the database pool and drops all reusable databases, awaiting completion
+    ///
+    /// Named `close` since [`shutdown`](Self::shutdown) only rejects further pulls and creations.
+    /// Attempts to drop every database, failing with all errors encountered,
+    /// which dropping the database pool swallows instead.
+    ///
+    /// Reusable connection pools borrow the database pool, so none can be checked out when closing it.
+    /// Single-use connection pools and connections drop their own databases when dropped.
+    pub async fn close(
+        self,
+    ) -> Result<(), Vec<Error<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>>>
+    {
+        self.shutdown();
+        let errors = join_all(
+            self.object_pool
+                .drain()
+                .into_iter()
+                .map(ReusableConnectionPoolInner::close),
+        )
+        .await
+        .into_iter()
+        .filter_map(Result::err)
+        .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns statistics of reusable databases without querying the database server
+    #[must_use]
+    pub fn stats(&self) -> PoolStats {
+        let created = self.object_pool.created();
+        let available = self.object_pool.available();
+        // Databases detached after a panic or a failed clean are neither in use nor available
+        let detached = self.object_pool.detached();
+        PoolStats {
+            created,
+            in_use: created.saturating_sub(available + detached),
+            available,
+        }
+    }
+
+    /// Returns how long each phase of creating the database pool took
+    #[must_use]
+    pub fn provision_timings(&self) -> ProvisionTimings {
+        self.provision_timings
+    }
+
     /// Pulls a reusable connection pool
     ///
     /// Privileges are granted only for ``SELECT``, ``INSERT``, ``UPDATE``, and ``DELETE`` operations.
+    /// # Panics
+    /// Panics if the database pool has been shut down.
+    /// Use [`try_pull_immutable`](Self::try_pull_immutable) to handle this case gracefully.
     /// # Example
     /// ```
     /// use bb8::Pool;
@@ -63,7 +195,30 @@ impl<B: Backend> DatabasePool<B> {
     /// ```
     #[must_use]
     pub async fn pull_immutable(&self) -> ReusableConnectionPool<B> {
-        self.object_pool.pull().await
+        self.try_pull_immutable().await.expect(
+            "database pool must not be shut down, use try_pull_immutable to handle shutdown",
+        )
+    }
+
+    /// Pulls a reusable connection pool, failing if the database pool has been shut down
+    ///
+    /// Privileges are granted only for ``SELECT``, ``INSERT``, ``UPDATE``, and ``DELETE`` operations.
+    pub async fn try_pull_immutable(
+        &self,
+    ) -> Result<
+        ReusableConnectionPool<B>,
+        Error<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        if self.is_shut_down.load(Ordering::Acquire) {
+            return Err(Error::PoolShutdown);
+        }
+        let permit = self.acquire_checkout().await;
+        let mut conn_pool = self.object_pool.pull().await;
+        conn_pool.record_hand_out();
+        if let Some(permit) = permit {
+            conn_pool.hold(permit);
+        }
+        Ok(conn_pool)
     }
 
     /// Creates a single-use connection pool
@@ -115,7 +270,46 @@ impl<B: Backend> DatabasePool<B> {
         SingleUseConnectionPool<B>,
         Error<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
     > {
-        SingleUseConnectionPool::new(self.backend.clone()).await
+        if self.is_shut_down.load(Ordering::Acquire) {
+            return Err(Error::PoolShutdown);
+        }
+        let permit = self.acquire_checkout().await;
+        let mut conn_pool =
+            SingleUseConnectionPool::new(self.backend.clone(), self.stats.clone()).await?;
+        if let Some(permit) = permit {
+            conn_pool.hold(permit);
+        }
+        Ok(conn_pool)
+    }
+
+    /// Creates a single-use database and establishes a single connection to it
+    /// instead of building a connection pool
+    ///
+    /// All privileges are granted. The database is dropped once the connection is dropped.
+    pub async fn pull_connection(
+        &self,
+    ) -> Result<
+        SingleUseConnection<B>,
+        Error<B::BuildError, B::PoolError, B::ConnectionError, B::QueryError>,
+    > {
+        if self.is_shut_down.load(Ordering::Acquire) {
+            return Err(Error::PoolShutdown);
+        }
+        let permit = self.acquire_checkout().await;
+        let mut conn = SingleUseConnection::new(self.backend.clone(), self.stats.clone()).await?;
+        if let Some(permit) = permit {
+            conn.hold(permit);
+        }
+        Ok(conn)
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<B: Backend> Drop for DatabasePool<B> {
+    fn drop(&mut self) {
+        if self.report_summary_flag {
+            self.stats.report();
+        }
     }
 }
 
@@ -168,33 +362,58 @@ pub trait DatabasePoolBuilder: Backend {
         DatabasePool<Self>,
         Error<Self::BuildError, Self::PoolError, Self::ConnectionError, Self::QueryError>,
     > {
+        let start = Instant::now();
         self.init().await?;
+        let init = start.elapsed();
+
         let backend = Arc::new(self);
+        let stats = Arc::new(Stats::default());
         let object_pool = {
+            let detach_on_panic = backend.get_keep_databases_on_panic();
             let backend = backend.clone();
+            let stats = stats.clone();
             ObjectPool::new(
                 move || {
                     let backend = backend.clone();
+                    let stats = stats.clone();
                     Box::pin(async {
-                        ReusableConnectionPoolInner::new(backend)
+                        ReusableConnectionPoolInner::new(backend, stats)
                             .await
                             .expect("connection pool creation must succeed")
                     })
                 },
                 |mut conn_pool| {
                     Box::pin(async {
-                        conn_pool
-                            .clean()
-                            .await
-                            .expect("connection pool cleaning must succeed");
-                        conn_pool
+                        // Drop database failing to clean instead of handing it out
+                        conn_pool.clean().await.ok().map(|()| conn_pool)
                     })
                 },
             )
+            .detach_on_panic(detach_on_panic)
         };
+
+        // Create initial databases concurrently, making them available for reuse
+        // and failing with the first creation error once the others have completed
+        let start = Instant::now();
+        object_pool
+            .try_fill(backend.get_initial_size(), || {
+                ReusableConnectionPoolInner::new(backend.clone(), stats.clone())
+            })
+            .await?;
+        let initial_creations = start.elapsed();
+
         Ok(DatabasePool {
             backend,
             object_pool,
+            stats,
+            provision_timings: ProvisionTimings {
+                init,
+                initial_creations,
+            },
+            checked_out_databases: None,
+            #[cfg(feature = "tracing")]
+            report_summary_flag: false,
+            is_shut_down: AtomicBool::new(false),
         })
     }
 }
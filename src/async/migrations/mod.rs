@@ -0,0 +1,128 @@
+use diesel::{migration::MigrationSource, pg::Pg};
+use diesel_async::{AsyncPgConnection, async_connection_wrapper::AsyncConnectionWrapper};
+use diesel_migrations::MigrationHarness;
+use tokio::task::spawn_blocking;
+
+/// Error returned when running migrations fails
+pub type MigrationError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Runs pending [`Diesel`](https://docs.rs/diesel_migrations/2.2.0/diesel_migrations/) migrations on a connection
+/// passed to the `create_entities` closure of [`DieselAsyncPostgresBackend`](struct@super::DieselAsyncPostgresBackend)
+///
+/// Migration harnesses are synchronous, so the connection is wrapped in an
+/// [`AsyncConnectionWrapper`](https://docs.rs/diesel-async/0.5.2/diesel_async/async_connection_wrapper/type.AsyncConnectionWrapper.html)
+/// that blocks on each query, and migrations run on a blocking task so as not to block the async runtime.
+/// The wrapper does not give back the connection, so the closure should return `None` afterwards.
+/// # Example
+/// ```
+/// use bb8::Pool;
+/// use db_pool::{
+///     r#async::{DieselAsyncPostgresBackend, DieselBb8, run_pending_migrations},
+///     PrivilegedPostgresConfig,
+/// };
+/// use diesel_migrations::{EmbeddedMigrations, embed_migrations};
+/// use dotenvy::dotenv;
+///
+/// const MIGRATIONS: EmbeddedMigrations = embed_migrations!("src/async/migrations/test_migrations");
+///
+/// async fn f() {
+///     dotenv().ok();
+///
+///     let config = PrivilegedPostgresConfig::from_env().unwrap();
+///
+///     let backend = DieselAsyncPostgresBackend::<DieselBb8>::new(
+///         config,
+///         |_| Pool::builder().max_size(10),
+///         |_| Pool::builder().max_size(2),
+///         None,
+///         move |conn| {
+///             Box::pin(async move {
+///                 run_pending_migrations(conn, MIGRATIONS).await.unwrap();
+///                 None
+///             })
+///         },
+///     )
+///     .await
+///     .unwrap();
+/// }
+///
+/// tokio_test::block_on(f());
+/// ```
+pub async fn run_pending_migrations(
+    conn: AsyncPgConnection,
+    migrations: impl MigrationSource<Pg> + Send + 'static,
+) -> Result<(), MigrationError> {
+    let mut conn: AsyncConnectionWrapper<AsyncPgConnection> = AsyncConnectionWrapper::from(conn);
+
+    spawn_blocking(move || conn.run_pending_migrations(migrations).map(|_| ())).await?
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused_variables, clippy::unwrap_used)]
+
+    use bb8::Pool;
+    use diesel::{dsl::exists, prelude::*, select, table};
+    use diesel_async::RunQueryDsl;
+    use diesel_migrations::{EmbeddedMigrations, embed_migrations};
+    use dotenvy::dotenv;
+    use tokio_shared_rt::test;
+
+    use crate::{
+        r#async::{DieselAsyncPostgresBackend, DieselBb8, db_pool::DatabasePoolBuilder},
+        common::config::PrivilegedPostgresConfig,
+        tests::PG_DROP_LOCK,
+    };
+
+    use super::run_pending_migrations;
+
+    const MIGRATIONS: EmbeddedMigrations =
+        embed_migrations!("src/async/migrations/test_migrations");
+
+    table! {
+        pg_tables (tablename) {
+            tablename -> Text
+        }
+    }
+
+    #[test(flavor = "multi_thread", shared)]
+    async fn runs_pending_migrations() {
+        dotenv().ok();
+
+        let config = PrivilegedPostgresConfig::from_env().unwrap();
+
+        let backend = DieselAsyncPostgresBackend::<DieselBb8>::new(
+            config,
+            |_| Pool::builder(),
+            |_| Pool::builder(),
+            None,
+            move |conn| {
+                Box::pin(async move {
+                    run_pending_migrations(conn, MIGRATIONS).await.unwrap();
+                    None
+                })
+            },
+        )
+        .await
+        .unwrap()
+        .drop_previous_databases(false);
+
+        let guard = PG_DROP_LOCK.read().await;
+
+        let db_pool = backend.create_database_pool().await.unwrap();
+        let conn_pool = db_pool.pull_immutable().await;
+        let conn = &mut conn_pool.get().await.unwrap();
+
+        // tables from both migrations must exist
+        for table_name in ["book", "author"] {
+            assert!(
+                select(exists(
+                    pg_tables::table.filter(pg_tables::tablename.eq(table_name))
+                ))
+                .get_result::<bool>(conn)
+                .await
+                .unwrap()
+            );
+        }
+    }
+}
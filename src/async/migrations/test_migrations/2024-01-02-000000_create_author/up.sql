@@ -0,0 +1 @@
+CREATE TABLE author(id SERIAL PRIMARY KEY, name TEXT NOT NULL);
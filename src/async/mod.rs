@@ -1,12 +1,18 @@
 mod backend;
 mod conn_pool;
 mod db_pool;
+#[cfg(feature = "diesel-async-migrations")]
+mod migrations;
 mod object_pool;
+mod stats;
 mod wrapper;
 
 pub use backend::*;
-pub use conn_pool::SingleUseConnectionPool;
+pub use conn_pool::{SingleUseConnection, SingleUseConnectionPool};
 pub use db_pool::{
-    DatabasePool, DatabasePoolBuilder as DatabasePoolBuilderTrait, ReusableConnectionPool,
+    DatabasePool, DatabasePoolBuilder as DatabasePoolBuilderTrait, PoolStats, ProvisionTimings,
+    ReusableConnectionPool,
 };
+#[cfg(feature = "diesel-async-migrations")]
+pub use migrations::{MigrationError, run_pending_migrations};
 pub use wrapper::PoolWrapper;
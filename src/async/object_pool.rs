@@ -1,42 +1,116 @@
 // adapted from https://github.com/CJP10/object-pool and https://github.com/EVaillant/lockfree-object-pool
 
+use futures::{StreamExt, stream::FuturesUnordered};
 use parking_lot::Mutex;
 use std::future::Future;
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::OwnedSemaphorePermit;
 
 type Stack<T> = Vec<T>;
 type Init<T> =
     Box<dyn Fn() -> Pin<Box<dyn Future<Output = T> + Send + 'static>> + Send + Sync + 'static>;
-type Reset<T> =
-    Box<dyn Fn(T) -> Pin<Box<dyn Future<Output = T> + Send + 'static>> + Send + Sync + 'static>;
+type Reset<T> = Box<
+    dyn Fn(T) -> Pin<Box<dyn Future<Output = Option<T>> + Send + 'static>> + Send + Sync + 'static,
+>;
 
 pub(crate) struct ObjectPool<T> {
     objects: Mutex<Stack<T>>,
     init: Init<T>,
     reset: Reset<T>,
+    created: AtomicUsize,
+    detached: AtomicUsize,
+    detach_on_panic: bool,
 }
 
 impl<T> ObjectPool<T> {
     pub(crate) fn new(
         init: impl Fn() -> Pin<Box<dyn Future<Output = T> + Send + 'static>> + Send + Sync + 'static,
-        reset: impl Fn(T) -> Pin<Box<dyn Future<Output = T> + Send + 'static>> + Send + Sync + 'static,
+        reset: impl Fn(T) -> Pin<Box<dyn Future<Output = Option<T>> + Send + 'static>>
+        + Send
+        + Sync
+        + 'static,
     ) -> ObjectPool<T> {
         ObjectPool {
             objects: Mutex::new(Vec::new()),
             init: Box::new(init),
             reset: Box::new(reset),
+            created: AtomicUsize::new(0),
+            detached: AtomicUsize::new(0),
+            detach_on_panic: false,
+        }
+    }
+
+    pub(crate) fn detach_on_panic(self, value: bool) -> Self {
+        Self {
+            detach_on_panic: value,
+            ..self
         }
     }
 
     pub(crate) async fn pull(&self) -> Reusable<T> {
-        let object = self.objects.lock().pop();
-        let object = if let Some(object) = object {
-            (self.reset)(object).await
-        } else {
-            (self.init)().await
-        };
-        Reusable::new(self, object)
+        loop {
+            let object = self.objects.lock().pop();
+            let Some(object) = object else {
+                let object = (self.init)().await;
+                self.created.fetch_add(1, Ordering::Relaxed);
+                return Reusable::new(self, object);
+            };
+
+            // Objects failing to reset are dropped instead of being reused
+            match (self.reset)(object).await {
+                Some(object) => return Reusable::new(self, object),
+                None => {
+                    self.detached.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Fills the pool with objects created concurrently by the given fallible initializer,
+    /// failing with the first error without adding any object
+    pub(crate) async fn try_fill<E, F: Future<Output = Result<T, E>>>(
+        &self,
+        count: usize,
+        init: impl Fn() -> F,
+    ) -> Result<(), E> {
+        // Poll creations without keeping their errors around, which need not be `Send`
+        let mut creations = (0..count).map(|_| init()).collect::<FuturesUnordered<_>>();
+        let mut objects = Vec::with_capacity(count);
+        while let Some(object) = creations.next().await {
+            match object {
+                Ok(object) => objects.push(object),
+                Err(err) => {
+                    // Let remaining creations complete instead of cancelling them midway,
+                    // dropping their objects along with those created so far
+                    let remaining = tokio::task::block_in_place(|| {
+                        tokio::runtime::Handle::current().block_on(creations.collect::<Vec<_>>())
+                    });
+                    drop(remaining);
+                    return Err(err);
+                }
+            }
+        }
+        self.created.fetch_add(objects.len(), Ordering::Relaxed);
+        self.objects.lock().extend(objects);
+        Ok(())
+    }
+
+    pub(crate) fn created(&self) -> usize {
+        self.created.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn detached(&self) -> usize {
+        self.detached.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn available(&self) -> usize {
+        self.objects.lock().len()
+    }
+
+    pub(crate) fn drain(&self) -> Vec<T> {
+        std::mem::take(&mut *self.objects.lock())
     }
 
     fn attach(&self, t: T) {
@@ -48,6 +122,8 @@ impl<T> ObjectPool<T> {
 pub struct Reusable<'a, T> {
     pool: &'a ObjectPool<T>,
     data: Option<T>,
+    // Released only after the object is attached back to the pool
+    permit: Option<OwnedSemaphorePermit>,
 }
 
 impl<'a, T> Reusable<'a, T> {
@@ -55,8 +131,13 @@ impl<'a, T> Reusable<'a, T> {
         Self {
             pool,
             data: Some(t),
+            permit: None,
         }
     }
+
+    pub(crate) fn hold(&mut self, permit: OwnedSemaphorePermit) {
+        self.permit = Some(permit);
+    }
 }
 
 const DATA_MUST_CONTAIN_SOME: &str = "data must always contain a [Some] value";
@@ -80,8 +161,13 @@ impl<T> DerefMut for Reusable<'_, T> {
 impl<T> Drop for Reusable<'_, T> {
     #[inline]
     fn drop(&mut self) {
-        self.pool
-            .attach(self.data.take().expect(DATA_MUST_CONTAIN_SOME));
+        let data = self.data.take().expect(DATA_MUST_CONTAIN_SOME);
+        // Objects of a panicking thread are dropped instead of being reused
+        if self.pool.detach_on_panic && std::thread::panicking() {
+            self.pool.detached.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.pool.attach(data);
+        }
     }
 }
 
@@ -89,6 +175,7 @@ impl<T> Drop for Reusable<'_, T> {
 mod tests {
     use super::ObjectPool;
     use std::mem::drop;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     impl<T> ObjectPool<T> {
         fn len(&self) -> usize {
@@ -101,7 +188,7 @@ mod tests {
         {
             let pool = ObjectPool::new(
                 || Box::pin(async { Vec::<u8>::new() }),
-                |obj| Box::pin(async { obj }),
+                |obj| Box::pin(async { Some(obj) }),
             );
 
             let object1 = pool.pull().await;
@@ -115,7 +202,7 @@ mod tests {
         {
             let pool = ObjectPool::new(
                 || Box::pin(async { Vec::<u8>::new() }),
-                |obj| Box::pin(async { obj }),
+                |obj| Box::pin(async { Some(obj) }),
             );
 
             let object1 = pool.pull().await;
@@ -127,11 +214,118 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn fill() {
+        let pool = ObjectPool::new(
+            || Box::pin(async { Vec::<u8>::new() }),
+            |obj| Box::pin(async { Some(obj) }),
+        );
+
+        assert_eq!(
+            pool.try_fill(3, || async { Ok::<_, ()>(Vec::<u8>::new()) })
+                .await,
+            Ok(())
+        );
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.created(), 3);
+
+        let object = pool.pull().await;
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.created(), 3);
+        drop(object);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fill_fails_without_adding_objects() {
+        let pool = ObjectPool::new(
+            || Box::pin(async { Vec::<u8>::new() }),
+            |obj| Box::pin(async { Some(obj) }),
+        );
+
+        let counter = AtomicUsize::new(0);
+        let result = pool
+            .try_fill(3, || async {
+                if counter.fetch_add(1, Ordering::Relaxed) == 1 {
+                    Err("creation failed")
+                } else {
+                    Ok(Vec::<u8>::new())
+                }
+            })
+            .await;
+        assert_eq!(result, Err("creation failed"));
+        assert_eq!(pool.len(), 0);
+        assert_eq!(pool.created(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fill_completes_remaining_creations_on_failure() {
+        let pool = ObjectPool::new(
+            || Box::pin(async { Vec::<u8>::new() }),
+            |obj| Box::pin(async { Some(obj) }),
+        );
+
+        let counter = AtomicUsize::new(0);
+        let completed = AtomicUsize::new(0);
+        let result = pool
+            .try_fill(3, || async {
+                if counter.fetch_add(1, Ordering::Relaxed) == 1 {
+                    return Err("creation failed");
+                }
+                tokio::task::yield_now().await;
+                completed.fetch_add(1, Ordering::Relaxed);
+                Ok(Vec::<u8>::new())
+            })
+            .await;
+        assert_eq!(result, Err("creation failed"));
+        assert_eq!(completed.load(Ordering::Relaxed), 2);
+        assert_eq!(pool.len(), 0);
+        assert_eq!(pool.created(), 0);
+    }
+
+    #[tokio::test]
+    async fn detaches_objects_dropped_while_panicking() {
+        let pool = ObjectPool::new(
+            || Box::pin(async { Vec::<u8>::new() }),
+            |obj| Box::pin(async { Some(obj) }),
+        )
+        .detach_on_panic(true);
+
+        let object = pool.pull().await;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            let _object = object;
+            panic!("test panicked");
+        }));
+        assert!(result.is_err());
+
+        // object must not be reused but counted as detached
+        assert_eq!(pool.len(), 0);
+        assert_eq!(pool.created(), 1);
+        assert_eq!(pool.detached(), 1);
+    }
+
+    #[tokio::test]
+    async fn detaches_objects_failing_to_reset() {
+        let pool = ObjectPool::new(
+            || Box::pin(async { Vec::<u8>::new() }),
+            |obj: Vec<u8>| Box::pin(async { obj.is_empty().then_some(obj) }),
+        );
+
+        let mut object = pool.pull().await;
+        object.push(1);
+        drop(object);
+
+        // object must be replaced by a newly created one
+        let object = pool.pull().await;
+        assert!(object.is_empty());
+        assert_eq!(pool.created(), 2);
+        assert_eq!(pool.detached(), 1);
+    }
+
     #[tokio::test]
     async fn e2e() {
         let pool = ObjectPool::new(
             || Box::pin(async { Vec::<u8>::new() }),
-            |obj| Box::pin(async { obj }),
+            |obj| Box::pin(async { Some(obj) }),
         );
         let mut objects = Vec::new();
 
@@ -156,7 +350,7 @@ mod tests {
             |mut v| {
                 Box::pin(async {
                     v.clear();
-                    v
+                    Some(v)
                 })
             },
         );
@@ -172,7 +366,7 @@ mod tests {
     async fn no_reset() {
         let pool = ObjectPool::new(
             || Box::pin(async { Vec::new() }),
-            |obj| Box::pin(async { obj }),
+            |obj| Box::pin(async { Some(obj) }),
         );
 
         let mut object = pool.pull().await;
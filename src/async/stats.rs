@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+#[derive(Default)]
+struct Counters {
+    created: usize,
+    live: usize,
+    max_live: usize,
+    cleaned: usize,
+    create_duration: Duration,
+    clean_duration: Duration,
+}
+
+/// Database pool activity statistics
+#[derive(Default)]
+pub(crate) struct Stats(Mutex<Counters>);
+
+impl Stats {
+    pub(crate) fn record_create(&self, duration: Duration) {
+        let mut counters = self.0.lock();
+        counters.created += 1;
+        counters.live += 1;
+        counters.max_live = counters.max_live.max(counters.live);
+        counters.create_duration += duration;
+    }
+
+    pub(crate) fn record_clean(&self, duration: Duration) {
+        let mut counters = self.0.lock();
+        counters.cleaned += 1;
+        counters.clean_duration += duration;
+    }
+
+    pub(crate) fn record_drop(&self) {
+        let mut counters = self.0.lock();
+        counters.live = counters.live.saturating_sub(1);
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn report(&self) {
+        let counters = self.0.lock();
+        let average = |total: Duration, count: usize| {
+            u32::try_from(count)
+                .ok()
+                .and_then(|count| total.checked_div(count))
+                .unwrap_or_default()
+        };
+
+        tracing::info!(
+            created = counters.created,
+            max_concurrent = counters.max_live,
+            cleaned = counters.cleaned,
+            average_create_duration = ?average(counters.create_duration, counters.created),
+            average_clean_duration = ?average(counters.clean_duration, counters.cleaned),
+            "database pool summary"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    #[test]
+    fn tracks_max_concurrent_databases() {
+        let stats = Stats::default();
+
+        stats.record_create(Duration::from_millis(10));
+        stats.record_create(Duration::from_millis(30));
+        stats.record_drop();
+        stats.record_create(Duration::from_millis(20));
+
+        let counters = stats.0.lock();
+        assert_eq!(counters.created, 3);
+        assert_eq!(counters.live, 2);
+        assert_eq!(counters.max_live, 2);
+        assert_eq!(counters.create_duration, Duration::from_millis(60));
+    }
+
+    #[test]
+    fn accumulates_cleans() {
+        let stats = Stats::default();
+
+        stats.record_clean(Duration::from_millis(5));
+        stats.record_clean(Duration::from_millis(15));
+
+        let counters = stats.0.lock();
+        assert_eq!(counters.cleaned, 2);
+        assert_eq!(counters.clean_duration, Duration::from_millis(20));
+    }
+}
@@ -5,12 +5,7 @@
     feature = "sea-orm-mysql"
 ))]
 pub(crate) mod mysql;
-#[cfg(any(
-    test,
-    feature = "diesel-postgres",
-    feature = "diesel-async-postgres",
-    feature = "sea-orm-postgres"
-))]
+#[cfg(any(test, feature = "_postgres"))]
 pub(crate) mod postgres;
 
 #[cfg(any(
@@ -19,9 +14,5 @@ pub(crate) mod postgres;
     feature = "sea-orm-mysql"
 ))]
 pub use mysql::PrivilegedMySQLConfig;
-#[cfg(any(
-    feature = "diesel-postgres",
-    feature = "diesel-async-postgres",
-    feature = "sea-orm-postgres"
-))]
+#[cfg(feature = "_postgres")]
 pub use postgres::PrivilegedPostgresConfig;
@@ -2,3 +2,5 @@
 pub mod mysql;
 #[cfg(any(feature = "_sync-postgres", feature = "_async-postgres"))]
 pub mod postgres;
+#[cfg(feature = "_sync-sqlite")]
+pub mod sqlite;
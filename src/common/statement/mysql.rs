@@ -32,6 +32,10 @@ pub fn get_table_names(db_name: &str) -> String {
     format!("SELECT table_name FROM information_schema.tables WHERE table_schema = '{db_name}'")
 }
 
+pub fn reset_auto_increment(table_name: &str, db_name: &str) -> String {
+    format!("ALTER TABLE {db_name}.{table_name} AUTO_INCREMENT = 1")
+}
+
 pub fn truncate_table(table_name: &str, db_name: &str) -> String {
     format!("TRUNCATE TABLE {db_name}.{table_name}")
 }
@@ -1,49 +1,348 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 #[allow(dead_code)]
 pub const GET_DATABASE_NAMES: &str =
     "SELECT datname FROM pg_catalog.pg_database WHERE datname LIKE 'db_pool_%'";
 
 #[allow(dead_code)]
-pub const GET_TABLE_NAMES: &str = "SELECT tablename FROM pg_catalog.pg_tables WHERE schemaname != 'pg_catalog' AND schemaname != 'information_schema'";
+pub const GET_DATABASE_NAMES_WITH_PREFIX: &str =
+    "SELECT datname FROM pg_catalog.pg_database WHERE starts_with(datname, $1) ORDER BY datname";
+
+#[allow(dead_code)]
+pub const GET_DATABASE_COMMENTS_WITH_PREFIX: &str = "SELECT datname, shobj_description(oid, 'pg_database') AS description FROM pg_catalog.pg_database WHERE starts_with(datname, $1) ORDER BY datname";
+
+#[allow(dead_code)]
+pub const GET_TABLE_NAMES: &str = "SELECT tablename, schemaname FROM pg_catalog.pg_tables WHERE schemaname != 'pg_catalog' AND schemaname != 'information_schema'";
+
+#[allow(dead_code)]
+pub const GET_PARTITION_NAMES: &str = "SELECT c.relname AS tablename, n.nspname AS schemaname FROM pg_catalog.pg_class c JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace WHERE c.relispartition";
+
+#[allow(dead_code)]
+pub const GET_SEQUENCE_NAMES: &str = "SELECT sequencename, schemaname FROM pg_catalog.pg_sequences WHERE schemaname != 'pg_catalog' AND schemaname != 'information_schema'";
+
+#[allow(dead_code)]
+pub const GET_ROLE_ATTRIBUTES: &str = "SELECT rolsuper, rolcreatedb, rolcreaterole FROM pg_catalog.pg_roles WHERE rolname = current_user";
+
+#[allow(dead_code)]
+pub const ANALYZE: &str = "ANALYZE";
+
+#[allow(dead_code)]
+pub const BEGIN: &str = "BEGIN";
+
+#[allow(dead_code)]
+pub const COMMIT: &str = "COMMIT";
+
+#[allow(dead_code)]
+pub const VACUUM: &str = "VACUUM";
+
+#[allow(dead_code)]
+pub const RESET_LOCK_TIMEOUT: &str = "RESET lock_timeout";
+
+#[allow(dead_code)]
+pub const SET_REPLICA_SESSION_ROLE: &str = "SET session_replication_role = replica";
+
+#[allow(dead_code)]
+pub const RESET_SESSION_ROLE: &str = "SET session_replication_role = DEFAULT";
+
+#[allow(dead_code)]
+pub const GET_SCHEMA_NAMES_WITH_PREFIX: &str = "SELECT schema_name FROM information_schema.schemata WHERE starts_with(schema_name, $1) ORDER BY schema_name";
 
 pub fn create_database(db_name: &str) -> String {
     format!("CREATE DATABASE {db_name}")
 }
 
-pub fn create_role(name: &str) -> String {
-    format!("CREATE ROLE {name} WITH LOGIN PASSWORD '{name}'")
+#[allow(dead_code)]
+pub fn create_database_from_template(
+    db_name: &str,
+    template: &str,
+    encoding: Option<&str>,
+    locale: Option<&str>,
+) -> String {
+    let mut stmt = format!("CREATE DATABASE {db_name} TEMPLATE {template}");
+    if let Some(encoding) = encoding {
+        stmt.push_str(format!(" ENCODING '{encoding}'").as_str());
+    }
+    if let Some(locale) = locale {
+        stmt.push_str(format!(" LOCALE '{locale}'").as_str());
+    }
+    stmt
+}
+
+#[allow(dead_code)]
+pub fn database_locale_clauses(
+    encoding: Option<&str>,
+    lc_collate: Option<&str>,
+    lc_ctype: Option<&str>,
+) -> String {
+    [
+        ("ENCODING", encoding),
+        ("LC_COLLATE", lc_collate),
+        ("LC_CTYPE", lc_ctype),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| value.map(|value| format!(" {key} '{value}'")))
+    .collect()
+}
+
+pub fn create_extension(name: &str) -> String {
+    format!("CREATE EXTENSION IF NOT EXISTS \"{name}\"")
+}
+
+pub fn create_role(name: &str, password: &str, valid_until: Option<SystemTime>) -> String {
+    create_role_with_attributes(name, password, valid_until, true, None)
+}
+
+#[allow(dead_code)]
+pub fn create_role_with_attributes(
+    name: &str,
+    password: &str,
+    valid_until: Option<SystemTime>,
+    login: bool,
+    connection_limit: Option<u32>,
+) -> String {
+    let password = password.replace('\'', "''");
+    let login = if login { "LOGIN" } else { "NOLOGIN" };
+    let mut stmt = format!("CREATE ROLE {name} WITH {login} PASSWORD '{password}'");
+    if let Some(connection_limit) = connection_limit {
+        stmt.push_str(format!(" CONNECTION LIMIT {connection_limit}").as_str());
+    }
+    if let Some(valid_until) = valid_until {
+        let valid_until = format_timestamp(valid_until);
+        stmt.push_str(format!(" VALID UNTIL '{valid_until}'").as_str());
+    }
+    stmt
+}
+
+const CREATION_TIME_COMMENT_PREFIX: &str = "db_pool created_at=";
+
+#[allow(dead_code)]
+pub fn comment_database_creation_time(db_name: &str, created_at: SystemTime) -> String {
+    let secs = created_at
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    format!("COMMENT ON DATABASE {db_name} IS '{CREATION_TIME_COMMENT_PREFIX}{secs}'")
+}
+
+#[allow(dead_code)]
+pub fn parse_database_creation_time(comment: &str) -> Option<SystemTime> {
+    comment
+        .strip_prefix(CREATION_TIME_COMMENT_PREFIX)
+        .and_then(|secs| secs.parse().ok())
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
 }
 
 pub fn grant_database_ownership(db_name: &str, role_name: &str) -> String {
     format!("ALTER DATABASE {db_name} OWNER to {role_name}")
 }
 
+pub fn set_database_parameter(db_name: &str, key: &str, value: &str) -> String {
+    let value = value.replace('\'', "''");
+    format!("ALTER DATABASE {db_name} SET {key} = '{value}'")
+}
+
+#[allow(dead_code)]
+pub fn set_lock_timeout(value: &str) -> String {
+    format!("SET lock_timeout = '{value}'")
+}
+
+pub fn grant_role(role_name: &str, member_name: &str) -> String {
+    format!("GRANT {role_name} TO {member_name}")
+}
+
+pub fn reassign_owned(from: &str, to: &str) -> String {
+    format!("REASSIGN OWNED BY {from} TO {to}")
+}
+
 pub fn grant_restricted_table_privileges(role_name: &str) -> String {
-    format!("GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA public TO {role_name}")
+    grant_table_privileges(
+        &["SELECT", "INSERT", "UPDATE", "DELETE"],
+        "public",
+        role_name,
+    )
+}
+
+pub fn grant_table_privileges(privileges: &[&str], schema_name: &str, role_name: &str) -> String {
+    let privileges = privileges.join(", ");
+    format!("GRANT {privileges} ON ALL TABLES IN SCHEMA {schema_name} TO {role_name}")
+}
+
+#[allow(dead_code)]
+pub fn grant_temporary_privilege(db_name: &str, role_name: &str) -> String {
+    format!("GRANT TEMPORARY ON DATABASE {db_name} TO {role_name}")
 }
 
 pub fn grant_restricted_sequence_privileges(role_name: &str) -> String {
     format!("GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA public TO {role_name}")
 }
 
-pub fn truncate_table(table_name: &str) -> String {
+pub fn quote_identifier(identifier: &str) -> String {
+    let identifier = identifier.replace('"', "\"\"");
+    format!("\"{identifier}\"")
+}
+
+fn qualify_identifier(schema_name: &str, name: &str) -> String {
+    let schema_name = quote_identifier(schema_name);
+    let name = quote_identifier(name);
+    format!("{schema_name}.{name}")
+}
+
+pub fn truncate_table(schema_name: &str, table_name: &str) -> String {
+    let table_name = qualify_identifier(schema_name, table_name);
     format!("TRUNCATE TABLE {table_name} RESTART IDENTITY CASCADE")
 }
 
+#[allow(dead_code)]
+pub fn truncate_tables(table_names: &[(&str, &str)]) -> String {
+    let table_names = table_names
+        .iter()
+        .map(|(schema_name, table_name)| qualify_identifier(schema_name, table_name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("TRUNCATE TABLE {table_names} RESTART IDENTITY CASCADE")
+}
+
+#[allow(dead_code)]
+pub fn delete_from_table(schema_name: &str, table_name: &str) -> String {
+    let table_name = qualify_identifier(schema_name, table_name);
+    format!("DELETE FROM {table_name}")
+}
+
+#[allow(dead_code)]
+pub fn restart_sequence(schema_name: &str, sequence_name: &str) -> String {
+    let sequence_name = qualify_identifier(schema_name, sequence_name);
+    format!("ALTER SEQUENCE {sequence_name} RESTART")
+}
+
 pub fn drop_database(db_name: &str) -> String {
     format!("DROP DATABASE {db_name}")
 }
 
+pub fn drop_database_if_exists(db_name: &str) -> String {
+    format!("DROP DATABASE IF EXISTS {db_name}")
+}
+
+#[allow(dead_code)]
+pub fn force_drop_database(db_name: &str) -> String {
+    format!("DROP DATABASE {db_name} WITH (FORCE)")
+}
+
+pub fn terminate_database_connections(db_name: &str) -> String {
+    format!(
+        "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = '{db_name}' AND pid <> pg_backend_pid()"
+    )
+}
+
+pub fn force_drop_database_if_exists(db_name: &str) -> String {
+    format!("DROP DATABASE IF EXISTS {db_name} WITH (FORCE)")
+}
+
 pub fn drop_role(name: &str) -> String {
     format!("DROP ROLE {name}")
 }
 
+#[allow(dead_code)]
+pub fn drop_role_if_exists(name: &str) -> String {
+    format!("DROP ROLE IF EXISTS {name}")
+}
+
+#[allow(dead_code)]
+pub fn create_schema(schema_name: &str) -> String {
+    format!("CREATE SCHEMA {schema_name}")
+}
+
+#[allow(dead_code)]
+pub fn grant_schema_ownership(schema_name: &str, role_name: &str) -> String {
+    format!("ALTER SCHEMA {schema_name} OWNER TO {role_name}")
+}
+
+#[allow(dead_code)]
+pub fn grant_schema_usage(schema_name: &str, role_name: &str) -> String {
+    format!("GRANT USAGE ON SCHEMA {schema_name} TO {role_name}")
+}
+
+#[allow(dead_code)]
+pub fn grant_restricted_schema_table_privileges(schema_name: &str, role_name: &str) -> String {
+    format!(
+        "GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA {schema_name} TO {role_name}"
+    )
+}
+
+#[allow(dead_code)]
+pub fn grant_restricted_schema_sequence_privileges(schema_name: &str, role_name: &str) -> String {
+    format!("GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA {schema_name} TO {role_name}")
+}
+
+#[allow(dead_code)]
+pub fn get_table_names(schema_name: &str) -> String {
+    format!(
+        "SELECT table_name FROM information_schema.tables WHERE table_schema = '{schema_name}' AND table_type = 'BASE TABLE'"
+    )
+}
+
+#[allow(dead_code)]
+pub fn get_sequence_names(schema_name: &str) -> String {
+    format!(
+        "SELECT sequence_name FROM information_schema.sequences WHERE sequence_schema = '{schema_name}'"
+    )
+}
+
+#[allow(dead_code)]
+pub fn drop_schema(schema_name: &str) -> String {
+    format!("DROP SCHEMA {schema_name} CASCADE")
+}
+
+// formats as a UTC timestamp literal, see https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn format_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}+00",
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{
+        comment_database_creation_time, create_database_from_template, create_role,
+        database_locale_clauses, parse_database_creation_time, restart_sequence,
+        set_database_parameter, truncate_table, truncate_tables,
+    };
+
     pub const CREATE_ENTITIES_STATEMENTS: [&str; 2] = [
         "CREATE TABLE book(id SERIAL PRIMARY KEY, title TEXT NOT NULL)",
         "CREATE TABLE dummy(id SERIAL PRIMARY KEY)",
     ];
 
+    #[allow(dead_code)]
+    pub const INSERT_BOOK: &str = "INSERT INTO book (title) VALUES ('Title')";
+
+    #[allow(dead_code)]
+    pub const CREATE_INVOICE_STATEMENTS: [&str; 2] = [
+        "CREATE SCHEMA billing",
+        "CREATE TABLE billing.invoice(id SERIAL PRIMARY KEY)",
+    ];
+
+    #[allow(dead_code)]
+    pub const CREATE_AUTHOR: &str = "CREATE TABLE author AS SELECT 1 AS id, 'Name'::text AS name";
+
     pub const DDL_STATEMENTS: [&str; 9] = [
         "CREATE TABLE author()",
         "ALTER TABLE book RENAME TO new_book",
@@ -62,4 +361,112 @@ pub(crate) mod tests {
         "UPDATE book SET title = 'Title 2' WHERE id = 1",
         "DELETE FROM book WHERE id = 1",
     ];
+
+    #[test]
+    fn create_role_without_validity() {
+        assert_eq!(
+            create_role("db_pool_role", "db_pool_role", None),
+            "CREATE ROLE db_pool_role WITH LOGIN PASSWORD 'db_pool_role'"
+        );
+    }
+
+    #[test]
+    fn create_role_with_custom_password() {
+        assert_eq!(
+            create_role("db_pool_role", "it's secret", None),
+            "CREATE ROLE db_pool_role WITH LOGIN PASSWORD 'it''s secret'"
+        );
+    }
+
+    #[test]
+    fn create_role_with_validity() {
+        let valid_until = UNIX_EPOCH + Duration::from_secs(1_709_210_096);
+        assert_eq!(
+            create_role("db_pool_role", "db_pool_role", Some(valid_until)),
+            "CREATE ROLE db_pool_role WITH LOGIN PASSWORD 'db_pool_role' VALID UNTIL '2024-02-29 12:34:56+00'"
+        );
+    }
+
+    #[test]
+    fn set_database_parameter_with_quoted_value() {
+        assert_eq!(
+            set_database_parameter("db_pool_db", "search_path", "'$user', public"),
+            "ALTER DATABASE db_pool_db SET search_path = '''$user'', public'"
+        );
+    }
+
+    #[test]
+    fn create_database_from_named_template() {
+        assert_eq!(
+            create_database_from_template("db_pool_db", "template_db", None, None),
+            "CREATE DATABASE db_pool_db TEMPLATE template_db"
+        );
+    }
+
+    #[test]
+    fn create_database_from_template0() {
+        assert_eq!(
+            create_database_from_template("db_pool_db", "template0", Some("UTF8"), Some("C")),
+            "CREATE DATABASE db_pool_db TEMPLATE template0 ENCODING 'UTF8' LOCALE 'C'"
+        );
+    }
+
+    #[test]
+    fn database_locale_clauses_skip_unset_settings() {
+        assert_eq!(database_locale_clauses(None, None, None), "");
+        assert_eq!(
+            database_locale_clauses(Some("UTF8"), Some("C"), None),
+            " ENCODING 'UTF8' LC_COLLATE 'C'"
+        );
+        assert_eq!(
+            database_locale_clauses(None, Some("C"), Some("en_US.UTF-8")),
+            " LC_COLLATE 'C' LC_CTYPE 'en_US.UTF-8'"
+        );
+    }
+
+    #[test]
+    fn truncate_multiple_tables() {
+        assert_eq!(
+            truncate_tables(&[("public", "book"), ("billing", "author")]),
+            r#"TRUNCATE TABLE "public"."book", "billing"."author" RESTART IDENTITY CASCADE"#
+        );
+    }
+
+    #[test]
+    fn truncate_table_with_quoted_identifiers() {
+        assert_eq!(
+            truncate_table("public", "User"),
+            r#"TRUNCATE TABLE "public"."User" RESTART IDENTITY CASCADE"#
+        );
+        assert_eq!(
+            truncate_table("public", "select"),
+            r#"TRUNCATE TABLE "public"."select" RESTART IDENTITY CASCADE"#
+        );
+        assert_eq!(
+            truncate_table("public", r#"my"table"#),
+            r#"TRUNCATE TABLE "public"."my""table" RESTART IDENTITY CASCADE"#
+        );
+    }
+
+    #[test]
+    fn restart_sequence_with_quoted_identifiers() {
+        assert_eq!(
+            restart_sequence("public", "User_id_seq"),
+            r#"ALTER SEQUENCE "public"."User_id_seq" RESTART"#
+        );
+    }
+
+    #[test]
+    fn database_creation_time_round_trips_through_comment() {
+        let created_at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(
+            comment_database_creation_time("db", created_at),
+            "COMMENT ON DATABASE db IS 'db_pool created_at=1700000000'"
+        );
+        assert_eq!(
+            parse_database_creation_time("db_pool created_at=1700000000"),
+            Some(created_at)
+        );
+        assert_eq!(parse_database_creation_time("other comment"), None);
+    }
 }
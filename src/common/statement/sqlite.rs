@@ -0,0 +1,13 @@
+pub const GET_TABLE_NAMES: &str = "SELECT name FROM sqlite_master WHERE type = 'table' AND (name NOT LIKE 'sqlite_%' OR name = 'sqlite_sequence')";
+
+pub fn delete_table(table_name: &str) -> String {
+    format!("DELETE FROM {table_name}")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    pub const CREATE_ENTITIES_STATEMENTS: [&str; 2] = [
+        "CREATE TABLE book(id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL)",
+        "CREATE TABLE dummy(id INTEGER PRIMARY KEY)",
+    ];
+}
@@ -5,6 +5,7 @@ pub enum Error<C: Debug, Q: Debug> {
     Pool(r2d2::Error),
     Connection(C),
     Query(Q),
+    Io(std::io::Error),
 }
 
 impl<C: Debug, Q: Debug> From<r2d2::Error> for Error<C, Q> {
@@ -12,3 +13,9 @@ impl<C: Debug, Q: Debug> From<r2d2::Error> for Error<C, Q> {
         Self::Pool(value)
     }
 }
+
+impl<C: Debug, Q: Debug> From<std::io::Error> for Error<C, Q> {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
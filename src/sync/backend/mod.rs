@@ -4,6 +4,8 @@ mod error;
 mod mysql;
 #[cfg(feature = "_sync-postgres")]
 mod postgres;
+#[cfg(feature = "_sync-sqlite")]
+mod sqlite;
 pub(crate) mod r#trait;
 
 pub(crate) use error::Error;
@@ -14,5 +16,7 @@ pub use mysql::MySQLBackend;
 #[cfg(feature = "diesel-postgres")]
 pub use postgres::DieselPostgresBackend;
 #[cfg(feature = "postgres")]
-pub use postgres::PostgresBackend;
+pub use postgres::{PostgresBackend, list_orphaned_databases};
+#[cfg(feature = "diesel-sqlite")]
+pub use sqlite::DieselSqliteBackend;
 pub use r#trait::Backend as BackendTrait;
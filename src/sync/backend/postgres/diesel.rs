@@ -8,7 +8,10 @@ use parking_lot::Mutex;
 use r2d2::{Builder, Pool, PooledConnection};
 use uuid::Uuid;
 
-use crate::{common::config::postgres::PrivilegedPostgresConfig, util::get_db_name};
+use crate::{
+    common::config::postgres::{PrivilegedPostgresConfig, RestrictedConnectionParams},
+    util::get_db_name,
+};
 
 use super::{
     super::{error::Error as BackendError, r#trait::Backend},
@@ -125,11 +128,9 @@ impl PostgresBackend for DieselPostgresBackend {
     ) -> ConnectionResult<PgConnection> {
         let db_name = get_db_name(db_id);
         let db_name = db_name.as_str();
-        let database_url = self.privileged_config.restricted_database_connection_url(
-            db_name,
-            Some(db_name),
-            db_name,
-        );
+        let database_url = self
+            .privileged_config
+            .restricted_connection_url(&RestrictedConnectionParams::for_database(db_name));
         PgConnection::establish(database_url.as_str())
     }
 
@@ -168,16 +169,14 @@ impl PostgresBackend for DieselPostgresBackend {
     ) -> Result<Pool<Self::ConnectionManager>, r2d2::Error> {
         let db_name = get_db_name(db_id);
         let db_name = db_name.as_str();
-        let database_url = self.privileged_config.restricted_database_connection_url(
-            db_name,
-            Some(db_name),
-            db_name,
-        );
+        let database_url = self
+            .privileged_config
+            .restricted_connection_url(&RestrictedConnectionParams::for_database(db_name));
         let manager = ConnectionManager::<PgConnection>::new(database_url.as_str());
         (self.create_restricted_pool)().build(manager)
     }
 
-    fn get_table_names(&self, conn: &mut PgConnection) -> QueryResult<Vec<String>> {
+    fn get_table_names(&self, conn: &mut PgConnection) -> QueryResult<Vec<(String, String)>> {
         table! {
             pg_tables (tablename) {
                 #[sql_name = "schemaname"]
@@ -188,7 +187,7 @@ impl PostgresBackend for DieselPostgresBackend {
 
         pg_tables::table
             .filter(pg_tables::schema_name.ne_all(["pg_catalog", "information_schema"]))
-            .select(pg_tables::tablename)
+            .select((pg_tables::schema_name, pg_tables::tablename))
             .load(conn)
     }
 
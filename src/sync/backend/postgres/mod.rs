@@ -7,4 +7,4 @@ mod r#trait;
 #[cfg(feature = "diesel-postgres")]
 pub use diesel::DieselPostgresBackend;
 #[cfg(feature = "postgres")]
-pub use postgres::PostgresBackend;
+pub use postgres::{PostgresBackend, list_orphaned_databases};
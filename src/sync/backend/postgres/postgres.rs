@@ -3,12 +3,16 @@ use std::{borrow::Cow, collections::HashMap, ops::Deref};
 use parking_lot::Mutex;
 use r2d2::{Builder, Pool, PooledConnection};
 use r2d2_postgres::{
-    postgres::{Client, Config, Error, NoTls},
+    postgres::{types::ToSql, Client, Config, Error, NoTls},
     PostgresConnectionManager,
 };
 use uuid::Uuid;
 
-use crate::{common::statement::postgres, util::get_db_name};
+use crate::{
+    common::statement::postgres,
+    util::{get_db_name, is_valid_db_name_prefix, list_prefixed_db_names},
+    PrivilegedPostgresConfig,
+};
 
 use super::{
     super::{error::Error as BackendError, r#trait::Backend},
@@ -82,6 +86,53 @@ impl PostgresBackend {
     }
 }
 
+/// Lists the names of databases created with the given database name prefix, including their snapshots
+///
+/// Intended for cleanup tooling running outside of Rust that drops databases left over from previous runs.
+/// Databases created by this crate are prefixed with `db_pool`.
+/// # Panics
+/// Panics if the prefix is not lowercase `[a-z0-9_]` starting with a letter or underscore,
+/// or is longer than 17 characters.
+/// # Example
+/// ```
+/// use db_pool::{sync::list_orphaned_databases, PrivilegedPostgresConfig};
+/// use dotenvy::dotenv;
+///
+/// dotenv().ok();
+///
+/// let config = PrivilegedPostgresConfig::from_env().unwrap();
+///
+/// for db_name in list_orphaned_databases(config, "db_pool").unwrap() {
+///     println!("{db_name}");
+/// }
+/// ```
+pub fn list_orphaned_databases(
+    config: PrivilegedPostgresConfig,
+    prefix: &str,
+) -> Result<Vec<String>, Error> {
+    assert!(
+        is_valid_db_name_prefix(prefix),
+        "invalid database name prefix {prefix:?}"
+    );
+
+    let mut conn = Config::from(config).connect(NoTls)?;
+    get_database_names(
+        &mut conn,
+        postgres::GET_DATABASE_NAMES_WITH_PREFIX,
+        &[&format!("{prefix}_")],
+    )
+    .map(|db_names| list_prefixed_db_names(prefix, db_names))
+}
+
+fn get_database_names(
+    conn: &mut Client,
+    query: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<Vec<String>, Error> {
+    conn.query(query, params)
+        .map(|rows| rows.iter().map(|row| row.get(0)).collect())
+}
+
 impl PostgresBackendTrait for PostgresBackend {
     type ConnectionManager = Manager;
     type ConnectionError = ConnectionError;
@@ -139,9 +190,7 @@ impl PostgresBackendTrait for PostgresBackend {
     }
 
     fn get_previous_database_names(&self, conn: &mut Client) -> Result<Vec<String>, QueryError> {
-        conn.query(postgres::GET_DATABASE_NAMES, &[])
-            .map(|rows| rows.iter().map(|row| row.get(0)).collect())
-            .map_err(Into::into)
+        get_database_names(conn, postgres::GET_DATABASE_NAMES, &[]).map_err(Into::into)
     }
 
     fn create_entities(&self, conn: &mut Client) {
@@ -159,9 +208,9 @@ impl PostgresBackendTrait for PostgresBackend {
         (self.create_restricted_pool)().build(manager)
     }
 
-    fn get_table_names(&self, conn: &mut Client) -> Result<Vec<String>, QueryError> {
+    fn get_table_names(&self, conn: &mut Client) -> Result<Vec<(String, String)>, QueryError> {
         conn.query(postgres::GET_TABLE_NAMES, &[])
-            .map(|rows| rows.iter().map(|row| row.get(0)).collect())
+            .map(|rows| rows.iter().map(|row| (row.get(1), row.get(0))).collect())
             .map_err(Into::into)
     }
 
@@ -252,6 +301,7 @@ mod tests {
 
     use dotenvy::dotenv;
     use r2d2::Pool;
+    use uuid::Uuid;
 
     use crate::{
         common::statement::postgres::tests::{
@@ -275,7 +325,7 @@ mod tests {
             test_backend_drops_previous_databases, test_pool_drops_created_restricted_databases,
             test_pool_drops_previous_databases,
         },
-        PostgresBackend,
+        get_db_name, list_orphaned_databases, Backend, PostgresBackend,
     };
 
     fn create_backend(with_table: bool) -> PostgresBackend {
@@ -339,6 +389,30 @@ mod tests {
         test_backend_drops_database(&backend, false);
     }
 
+    #[test]
+    fn lists_orphaned_databases() {
+        let backend = create_backend(false).drop_previous_databases(false);
+
+        let db_id = Uuid::new_v4();
+        let db_name = get_db_name(db_id);
+
+        let guard = lock_read();
+
+        backend.create(db_id, false).unwrap();
+
+        // created database must be listed
+        let config = PrivilegedPostgresConfig::from_env().unwrap();
+        let db_names = list_orphaned_databases(config, "db_pool").unwrap();
+        assert!(db_names.contains(&db_name));
+
+        // databases not matching the prefix must not be listed
+        let config = PrivilegedPostgresConfig::from_env().unwrap();
+        let db_names = list_orphaned_databases(config, "nonexistent").unwrap();
+        assert!(!db_names.contains(&db_name));
+
+        backend.drop(db_id, false).unwrap();
+    }
+
     #[test]
     fn pool_drops_previous_databases() {
         test_pool_drops_previous_databases(
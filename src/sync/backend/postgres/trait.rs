@@ -57,7 +57,7 @@ pub(super) trait PostgresBackend {
     fn get_table_names(
         &self,
         conn: &mut <Self::ConnectionManager as ManageConnection>::Connection,
-    ) -> Result<Vec<String>, Self::QueryError>;
+    ) -> Result<Vec<(String, String)>, Self::QueryError>;
 
     fn get_drop_previous_databases(&self) -> bool;
 }
@@ -117,7 +117,7 @@ impl<B: PostgresBackend> PostgresBackendWrapper<'_, B> {
                 .map_err(Into::into)?;
 
             // Create role
-            self.execute_query(postgres::create_role(db_name).as_str(), conn)
+            self.execute_query(postgres::create_role(db_name, db_name, None).as_str(), conn)
                 .map_err(Into::into)?;
         }
 
@@ -184,7 +184,9 @@ impl<B: PostgresBackend> PostgresBackendWrapper<'_, B> {
         // Generate truncate statements
         let stmts = table_names
             .iter()
-            .map(|table_name| postgres::truncate_table(table_name.as_str()).into());
+            .map(|(schema_name, table_name)| {
+                postgres::truncate_table(schema_name, table_name).into()
+            });
 
         // Truncate tables
         self.batch_execute_query(stmts, &mut conn)
@@ -240,7 +242,10 @@ pub(super) mod tests {
     use uuid::Uuid;
 
     use crate::{
-        common::statement::postgres::tests::{DDL_STATEMENTS, DML_STATEMENTS},
+        common::{
+            config::postgres::RestrictedConnectionParams,
+            statement::postgres::tests::{DDL_STATEMENTS, DML_STATEMENTS},
+        },
         r#sync::{backend::r#trait::Backend, db_pool::DatabasePoolBuilder},
         tests::{get_privileged_postgres_config, PG_DROP_LOCK},
         util::get_db_name,
@@ -276,7 +281,7 @@ pub(super) mod tests {
     fn create_restricted_connection_pool(db_name: &str) -> Pool {
         let config = get_privileged_postgres_config();
         let database_url =
-            config.restricted_database_connection_url(db_name, Some(db_name), db_name);
+            config.restricted_connection_url(&RestrictedConnectionParams::for_database(db_name));
         let manager = ConnectionManager::new(database_url);
         R2d2Pool::builder().build(manager).unwrap()
     }
@@ -0,0 +1,334 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use diesel::{
+    connection::SimpleConnection,
+    prelude::*,
+    r2d2::ConnectionManager,
+    result::{ConnectionError, Error},
+    sql_query,
+    sql_types::Text,
+    sqlite::SqliteConnection,
+};
+use r2d2::{Builder, Pool};
+use uuid::Uuid;
+
+use crate::{common::statement::sqlite, util::get_db_name};
+
+use super::super::{error::Error as BackendError, r#trait::Backend};
+
+type Manager = ConnectionManager<SqliteConnection>;
+
+const DATABASE_FILE_PREFIX: &str = "db_pool_";
+const DATABASE_FILE_EXTENSION: &str = "sqlite";
+
+/// [`Diesel SQLite`](https://docs.rs/diesel/2.2.11/diesel/sqlite/struct.SqliteConnection.html) backend
+///
+/// Each database is a separate file in a directory.
+/// Privileges cannot be restricted, so restricted and unrestricted databases are created alike.
+pub struct DieselSqliteBackend {
+    directory: PathBuf,
+    create_pool: Box<dyn Fn() -> Builder<Manager> + Send + Sync + 'static>,
+    create_entities: Box<dyn Fn(&mut SqliteConnection) + Send + Sync + 'static>,
+    drop_previous_databases_flag: bool,
+}
+
+impl DieselSqliteBackend {
+    /// Creates a new [`Diesel SQLite`](https://docs.rs/diesel/2.2.11/diesel/sqlite/struct.SqliteConnection.html) backend
+    ///
+    /// Database files are created in a `db_pool` directory inside the temporary directory of the system by default.
+    /// # Example
+    /// ```
+    /// use db_pool::sync::DieselSqliteBackend;
+    /// use diesel::{sql_query, RunQueryDsl};
+    /// use r2d2::Pool;
+    ///
+    /// let backend = DieselSqliteBackend::new(
+    ///     || Pool::builder().max_size(2),
+    ///     move |conn| {
+    ///         sql_query("CREATE TABLE book(id INTEGER PRIMARY KEY, title TEXT NOT NULL)")
+    ///             .execute(conn)
+    ///             .unwrap();
+    ///     },
+    /// );
+    /// ```
+    pub fn new(
+        create_pool: impl Fn() -> Builder<Manager> + Send + Sync + 'static,
+        create_entities: impl Fn(&mut SqliteConnection) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            directory: std::env::temp_dir().join("db_pool"),
+            create_pool: Box::new(create_pool),
+            create_entities: Box::new(create_entities),
+            drop_previous_databases_flag: true,
+        }
+    }
+
+    /// Create database files in the given directory
+    #[must_use]
+    pub fn directory(self, value: PathBuf) -> Self {
+        Self {
+            directory: value,
+            ..self
+        }
+    }
+
+    /// Drop databases created in previous runs upon initialization
+    #[must_use]
+    pub fn drop_previous_databases(self, value: bool) -> Self {
+        Self {
+            drop_previous_databases_flag: value,
+            ..self
+        }
+    }
+
+    fn get_database_path(&self, db_id: Uuid) -> PathBuf {
+        let db_name = get_db_name(db_id);
+        self.directory
+            .join(db_name)
+            .with_extension(DATABASE_FILE_EXTENSION)
+    }
+
+    fn establish_connection(path: &Path) -> ConnectionResult<SqliteConnection> {
+        SqliteConnection::establish(path.to_string_lossy().as_ref())
+    }
+
+    fn get_table_names(conn: &mut SqliteConnection) -> QueryResult<Vec<String>> {
+        #[derive(QueryableByName)]
+        struct Table {
+            #[diesel(sql_type = Text)]
+            name: String,
+        }
+
+        sql_query(sqlite::GET_TABLE_NAMES)
+            .load::<Table>(conn)
+            .map(|tables| tables.into_iter().map(|table| table.name).collect())
+    }
+}
+
+impl Backend for DieselSqliteBackend {
+    type ConnectionManager = Manager;
+    type ConnectionError = ConnectionError;
+    type QueryError = Error;
+
+    fn init(&self) -> Result<(), BackendError<ConnectionError, Error>> {
+        // Create directory for database files
+        fs::create_dir_all(&self.directory)?;
+
+        // Drop previous databases if needed
+        if self.drop_previous_databases_flag {
+            for entry in fs::read_dir(&self.directory)? {
+                let path = entry?.path();
+                let is_database = path
+                    .file_name()
+                    .and_then(|file_name| file_name.to_str())
+                    .is_some_and(|file_name| file_name.starts_with(DATABASE_FILE_PREFIX))
+                    && path
+                        .extension()
+                        .is_some_and(|extension| extension == DATABASE_FILE_EXTENSION);
+                if is_database {
+                    fs::remove_file(path)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn create(
+        &self,
+        db_id: Uuid,
+        _restrict_privileges: bool,
+    ) -> Result<Pool<Manager>, BackendError<ConnectionError, Error>> {
+        let path = self.get_database_path(db_id);
+
+        // Create database file by connecting to it
+        let conn = &mut Self::establish_connection(&path)?;
+
+        // Create entities
+        (self.create_entities)(conn);
+
+        // Create connection pool
+        let manager = Manager::new(path.to_string_lossy());
+        (self.create_pool)().build(manager).map_err(Into::into)
+    }
+
+    fn clean(&self, db_id: Uuid) -> Result<(), BackendError<ConnectionError, Error>> {
+        let path = self.get_database_path(db_id);
+        let conn = &mut Self::establish_connection(&path)?;
+
+        // Get table names, including the table holding autoincrement sequences
+        let table_names = Self::get_table_names(conn)?;
+
+        // Delete all rows
+        let stmts = table_names
+            .iter()
+            .map(|table_name| sqlite::delete_table(table_name.as_str()))
+            .collect::<Vec<_>>();
+        if !stmts.is_empty() {
+            conn.batch_execute(stmts.join(";").as_str())?;
+        }
+
+        Ok(())
+    }
+
+    fn drop(
+        &self,
+        db_id: Uuid,
+        _is_restricted: bool,
+    ) -> Result<(), BackendError<ConnectionError, Error>> {
+        // Delete database file
+        match fs::remove_file(self.get_database_path(db_id)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::borrow::Cow;
+
+    use diesel::{
+        Insertable, QueryDsl, RunQueryDsl, connection::SimpleConnection, insert_into, table,
+    };
+    use r2d2::Pool;
+    use uuid::Uuid;
+
+    use crate::{
+        common::statement::sqlite::tests::CREATE_ENTITIES_STATEMENTS,
+        sync::{backend::r#trait::Backend, db_pool::DatabasePoolBuilder},
+    };
+
+    use super::DieselSqliteBackend;
+
+    table! {
+        book (id) {
+            id -> Integer,
+            title -> Text
+        }
+    }
+
+    #[derive(Insertable)]
+    #[diesel(table_name = book)]
+    struct NewBook<'a> {
+        title: Cow<'a, str>,
+    }
+
+    fn create_backend() -> DieselSqliteBackend {
+        DieselSqliteBackend::new(Pool::builder, |conn| {
+            let query = CREATE_ENTITIES_STATEMENTS.join(";");
+            conn.batch_execute(query.as_str()).unwrap();
+        })
+        .directory(std::env::temp_dir().join(Uuid::new_v4().to_string()))
+    }
+
+    #[test]
+    fn backend_drops_previous_databases() {
+        let backend = create_backend();
+        backend.init().unwrap();
+
+        let db_id = Uuid::new_v4();
+        backend.create(db_id, true).unwrap();
+        let path = backend.get_database_path(db_id);
+        assert!(path.exists());
+
+        // file must be kept when not dropping previous databases
+        let backend = backend.drop_previous_databases(false);
+        backend.init().unwrap();
+        assert!(path.exists());
+
+        // file must be removed when dropping previous databases
+        let backend = backend.drop_previous_databases(true);
+        backend.init().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn backend_cleans_database() {
+        let backend = create_backend();
+        backend.init().unwrap();
+
+        let db_id = Uuid::new_v4();
+        let conn_pool = backend.create(db_id, true).unwrap();
+        let conn = &mut conn_pool.get().unwrap();
+
+        let insert_book = |conn: &mut _| {
+            insert_into(book::table)
+                .values(NewBook {
+                    title: "Title".into(),
+                })
+                .execute(conn)
+                .unwrap();
+        };
+
+        insert_book(conn);
+        backend.clean(db_id).unwrap();
+
+        // table must be empty
+        assert_eq!(book::table.count().get_result::<i64>(conn).unwrap(), 0);
+
+        // identity must be restarted
+        insert_book(conn);
+        assert_eq!(
+            book::table.select(book::id).load::<i32>(conn).unwrap(),
+            vec![1]
+        );
+
+        backend.drop(db_id, true).unwrap();
+    }
+
+    #[test]
+    fn backend_drops_database() {
+        let backend = create_backend();
+        backend.init().unwrap();
+
+        let db_id = Uuid::new_v4();
+        let conn_pool = backend.create(db_id, true).unwrap();
+        drop(conn_pool);
+
+        let path = backend.get_database_path(db_id);
+        assert!(path.exists());
+        backend.drop(db_id, true).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn pool_provides_isolated_databases() {
+        const NUM_DBS: i64 = 3;
+
+        let backend = create_backend();
+
+        let db_pool = backend.create_database_pool().unwrap();
+        let conn_pools = (0..NUM_DBS)
+            .map(|_| db_pool.pull_immutable())
+            .collect::<Vec<_>>();
+
+        // insert single row into each database
+        conn_pools.iter().enumerate().for_each(|(i, conn_pool)| {
+            let conn = &mut conn_pool.get().unwrap();
+            insert_into(book::table)
+                .values(NewBook {
+                    title: format!("Title {i}").into(),
+                })
+                .execute(conn)
+                .unwrap();
+        });
+
+        // rows fetched must be as inserted
+        conn_pools.iter().enumerate().for_each(|(i, conn_pool)| {
+            let conn = &mut conn_pool.get().unwrap();
+            assert_eq!(
+                book::table
+                    .select(book::title)
+                    .load::<String>(conn)
+                    .unwrap(),
+                vec![format!("Title {i}")]
+            );
+        });
+    }
+}
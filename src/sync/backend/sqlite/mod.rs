@@ -0,0 +1,5 @@
+#[cfg(feature = "diesel-sqlite")]
+mod diesel;
+
+#[cfg(feature = "diesel-sqlite")]
+pub use diesel::DieselSqliteBackend;
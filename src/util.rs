@@ -1,5 +1,147 @@
 use uuid::Uuid;
 
+pub const DEFAULT_DB_NAME_PREFIX: &str = "db_pool";
+
+#[cfg(any(feature = "_async-postgres", feature = "postgres"))]
+const SNAPSHOT_SUFFIX: &str = "_snapshot";
+
+// Longest database name not truncated by Postgres once suffixed for snapshots
+#[cfg(any(feature = "_async-postgres", feature = "postgres"))]
+const MAX_DB_NAME_LEN: usize = 63 - SNAPSHOT_SUFFIX.len();
+
+// Length of a UUID with hyphens replaced by underscores
+#[cfg(any(feature = "_async-postgres", feature = "postgres"))]
+const DB_ID_LEN: usize = 36;
+
 pub fn get_db_name(id: Uuid) -> String {
-    format!("db_pool_{}", id.to_string().replace('-', "_"))
+    get_prefixed_db_name(DEFAULT_DB_NAME_PREFIX, id)
+}
+
+pub fn get_prefixed_db_name(prefix: &str, id: Uuid) -> String {
+    format!("{prefix}_{}", id.to_string().replace('-', "_"))
+}
+
+#[cfg(feature = "_async-postgres")]
+pub fn get_snapshot_db_name(db_name: &str) -> String {
+    format!("{db_name}{SNAPSHOT_SUFFIX}")
+}
+
+#[cfg(any(feature = "_async-postgres", feature = "postgres"))]
+pub fn is_prefixed_db_name(prefix: &str, name: &str) -> bool {
+    let name = name.strip_suffix(SNAPSHOT_SUFFIX).unwrap_or(name);
+    name.strip_prefix(prefix)
+        .and_then(|name| name.strip_prefix('_'))
+        .is_some_and(|id| Uuid::try_parse(id.replace('_', "-").as_str()).is_ok())
+}
+
+/// Keeps names of databases created with the prefix, including their snapshots,
+/// out of those listed by `GET_DATABASE_NAMES_WITH_PREFIX`
+#[cfg(any(feature = "_async-postgres", feature = "postgres"))]
+pub fn list_prefixed_db_names(
+    prefix: &str,
+    db_names: impl IntoIterator<Item = String>,
+) -> Vec<String> {
+    db_names
+        .into_iter()
+        .filter(|db_name| is_prefixed_db_name(prefix, db_name))
+        .collect()
+}
+
+/// Whether the prefix is lowercase `[a-z0-9_]`, starting with a letter or underscore,
+/// and leaves room for the database ID and snapshot suffix within the identifier length limit
+#[cfg(any(feature = "_async-postgres", feature = "postgres"))]
+pub fn is_valid_db_name_prefix(prefix: &str) -> bool {
+    prefix
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && prefix
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && prefix.len() + 1 + DB_ID_LEN <= MAX_DB_NAME_LEN
+}
+
+/// Whether the generated name is the prefix followed by an underscore and lowercase `[a-z0-9_]`,
+/// leaving room for the snapshot suffix within the identifier length limit
+#[cfg(feature = "_async-postgres")]
+pub fn is_valid_generated_db_name(prefix: &str, name: &str) -> bool {
+    name.strip_prefix(prefix)
+        .and_then(|name| name.strip_prefix('_'))
+        .is_some_and(|suffix| {
+            !suffix.is_empty()
+                && suffix
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        })
+        && name.len() <= MAX_DB_NAME_LEN
+}
+
+/// Whether the name, or that of the database it snapshots, may have been generated with the prefix,
+/// excluding names derived from database IDs with longer prefixes
+#[cfg(feature = "_async-postgres")]
+pub fn is_generated_db_name(prefix: &str, name: &str) -> bool {
+    let name = name.strip_suffix(SNAPSHOT_SUFFIX).unwrap_or(name);
+    let has_longer_prefix = name.len() > prefix.len() + 1 + DB_ID_LEN
+        && name
+            .get(name.len() - DB_ID_LEN..)
+            .is_some_and(|id| Uuid::try_parse(id.replace('_', "-").as_str()).is_ok());
+    is_valid_generated_db_name(prefix, name) && !has_longer_prefix
+}
+
+#[cfg(all(test, feature = "_async-postgres"))]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{
+        get_prefixed_db_name, is_generated_db_name, is_valid_db_name_prefix,
+        is_valid_generated_db_name,
+    };
+
+    #[test]
+    fn accepts_lowercase_prefixes_within_length_limit() {
+        assert!(is_valid_db_name_prefix("db_pool"));
+        assert!(is_valid_db_name_prefix("_project_2"));
+        assert!(is_valid_db_name_prefix("seventeen_chars_x"));
+    }
+
+    #[test]
+    fn rejects_invalid_prefixes() {
+        assert!(!is_valid_db_name_prefix(""));
+        assert!(!is_valid_db_name_prefix("2db"));
+        assert!(!is_valid_db_name_prefix("Db_pool"));
+        assert!(!is_valid_db_name_prefix("db-pool"));
+        assert!(!is_valid_db_name_prefix("db_pool; DROP"));
+        assert!(!is_valid_db_name_prefix("eighteen_chars_xyz"));
+    }
+
+    #[test]
+    fn validates_generated_names() {
+        assert!(is_valid_generated_db_name("db_pool", "db_pool_test_1"));
+        assert!(!is_valid_generated_db_name("db_pool", "db_pool_"));
+        assert!(!is_valid_generated_db_name("db_pool", "db_pooltest_1"));
+        assert!(!is_valid_generated_db_name("db_pool", "other_test_1"));
+        assert!(!is_valid_generated_db_name("db_pool", "db_pool_Test_1"));
+        assert!(!is_valid_generated_db_name(
+            "db_pool",
+            format!("db_pool_{}", "x".repeat(50)).as_str()
+        ));
+    }
+
+    #[test]
+    fn recognizes_generated_names() {
+        assert!(is_generated_db_name("db_pool", "db_pool_test_1"));
+        assert!(is_generated_db_name("db_pool", "db_pool_test_1_snapshot"));
+        assert!(!is_generated_db_name("db_pool", "other_test_1"));
+
+        // names derived from database IDs must belong to their own prefix only
+        let db_id = Uuid::new_v4();
+        assert!(is_generated_db_name(
+            "db_pool",
+            get_prefixed_db_name("db_pool", db_id).as_str()
+        ));
+        assert!(!is_generated_db_name(
+            "db_pool",
+            get_prefixed_db_name("db_pool_other", db_id).as_str()
+        ));
+    }
 }